@@ -0,0 +1,239 @@
+//! Derive macros for `stringleton`
+//!
+//! You probably don't need to use this crate directly. Enable the `derive`
+//! feature of the [`stringleton`](../stringleton) crate instead, which
+//! re-exports the macros in this crate.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, LitStr, Path, parse_macro_input, spanned::Spanned};
+
+/// Map the unit variants of an enum to symbols, and back.
+///
+/// This generates two inherent methods on the enum:
+///
+/// - `fn symbol(&self) -> Symbol`, returning the symbol for the variant.
+/// - `fn from_symbol(symbol: Symbol) -> Option<Self>`, returning the variant
+///   for a symbol, if any.
+///
+/// The symbol of each variant is its name in `snake_case`, which can be
+/// overridden with a `#[symbol = "..."]` attribute on the variant. The symbols
+/// are created with [`static_sym!(...)`](../stringleton/macro.static_sym.html),
+/// so the crate must call [`enable!()`](../stringleton/macro.enable.html) in
+/// its root, and `from_symbol()` only compares pointers.
+///
+/// ```rust,ignore
+/// use stringleton::{SymbolEnum, sym};
+///
+/// #[derive(SymbolEnum, Debug, PartialEq)]
+/// enum Event {
+///     MouseDown,
+///     #[symbol = "up"]
+///     MouseUp,
+/// }
+///
+/// assert_eq!(Event::MouseDown.symbol(), sym!(mouse_down));
+/// assert_eq!(Event::from_symbol(sym!(up)), Some(Event::MouseUp));
+/// assert_eq!(Event::from_symbol(sym!(mouse_up)), None);
+/// ```
+///
+/// The generated code refers to the `stringleton` crate as `::stringleton`.
+/// When using `stringleton-dylib`, or when the crate is renamed, specify the
+/// path with `#[symbol(crate = ...)]` on the enum:
+///
+/// ```rust,ignore
+/// #[derive(stringleton_dylib::SymbolEnum)]
+/// #[symbol(crate = stringleton_dylib)]
+/// enum Event {
+///     MouseDown,
+///     MouseUp,
+/// }
+/// ```
+///
+/// Only enums where all variants are unit variants are supported, and two
+/// variants cannot map to the same symbol.
+#[proc_macro_derive(SymbolEnum, attributes(symbol))]
+pub fn derive_symbol_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    symbol_enum(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn symbol_enum(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`SymbolEnum` can only be derived for enums",
+        ));
+    };
+
+    let krate = crate_path(input)?;
+    let mut names: Vec<LitStr> = Vec::with_capacity(data.variants.len());
+    let mut variants = Vec::with_capacity(data.variants.len());
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new(
+                variant.fields.span(),
+                "`SymbolEnum` only supports unit variants",
+            ));
+        }
+        let name = variant_name(variant)?;
+        if let Some(previous) = names.iter().find(|other| other.value() == name.value()) {
+            let mut error =
+                syn::Error::new(name.span(), format!("duplicate symbol {:?}", name.value()));
+            error.combine(syn::Error::new(previous.span(), "first used here"));
+            return Err(error);
+        }
+        names.push(name);
+        variants.push(&variant.ident);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Get the symbol for this variant.
+            #[inline]
+            #[must_use]
+            pub fn symbol(&self) -> #krate::Symbol {
+                match self {
+                    #(Self::#variants => *#krate::static_sym!(#names),)*
+                }
+            }
+
+            /// Get the variant for `symbol`, if any.
+            #[must_use]
+            pub fn from_symbol(symbol: #krate::Symbol) -> ::core::option::Option<Self> {
+                #(
+                    if symbol == *#krate::static_sym!(#names) {
+                        return ::core::option::Option::Some(Self::#variants);
+                    }
+                )*
+                ::core::option::Option::None
+            }
+        }
+    })
+}
+
+/// Get the path of the `stringleton` crate from `#[symbol(crate = ...)]`.
+fn crate_path(input: &DeriveInput) -> syn::Result<Path> {
+    let mut krate = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("symbol") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("crate") {
+                krate = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown `symbol` attribute, expected `crate = ...`"))
+            }
+        })?;
+    }
+    Ok(krate.unwrap_or_else(|| syn::parse_quote!(::stringleton)))
+}
+
+/// Get the symbol name of a variant from `#[symbol = "..."]`, or its name in
+/// `snake_case`.
+fn variant_name(variant: &syn::Variant) -> syn::Result<LitStr> {
+    for attr in &variant.attrs {
+        if attr.path().is_ident("symbol") {
+            let value = &attr.meta.require_name_value()?.value;
+            return match value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(name),
+                    ..
+                }) => Ok(name.clone()),
+                _ => Err(syn::Error::new(
+                    value.span(),
+                    "expected a string literal, like `#[symbol = \"name\"]`",
+                )),
+            };
+        }
+    }
+    Ok(LitStr::new(
+        &snake_case(&variant.ident.to_string()),
+        variant.ident.span(),
+    ))
+}
+
+/// Convert a `CamelCase` identifier to `snake_case`, keeping acronyms together
+/// (`HttpServer` and `HTTPServer` both become `http_server`).
+fn snake_case(ident: &str) -> String {
+    let chars: Vec<char> = ident.trim_start_matches("r#").chars().collect();
+    let mut result = String::with_capacity(ident.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev != '_' && (!prev.is_uppercase() || next_is_lower) {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{snake_case, symbol_enum};
+    use syn::parse_quote;
+
+    fn error(input: &syn::DeriveInput) -> String {
+        symbol_enum(input)
+            .expect_err("expected the derive to fail")
+            .to_string()
+    }
+
+    #[test]
+    fn snake_case_names() {
+        assert_eq!(snake_case("Foo"), "foo");
+        assert_eq!(snake_case("MouseDown"), "mouse_down");
+        assert_eq!(snake_case("HttpServer"), "http_server");
+        assert_eq!(snake_case("HTTPServer"), "http_server");
+        assert_eq!(snake_case("Utf8"), "utf8");
+        assert_eq!(snake_case("Point3D"), "point3_d");
+        assert_eq!(snake_case("Already_Snake"), "already_snake");
+        assert_eq!(snake_case("r#Type"), "type");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            error(&parse_quote! {
+                enum Event {
+                    MouseDown { x: i32, y: i32 },
+                }
+            }),
+            "`SymbolEnum` only supports unit variants"
+        );
+        assert_eq!(
+            error(&parse_quote! {
+                enum Event {
+                    #[symbol = up]
+                    MouseUp,
+                }
+            }),
+            "expected a string literal, like `#[symbol = \"name\"]`"
+        );
+        assert_eq!(
+            error(&parse_quote! {
+                enum Event {
+                    MouseUp,
+                    #[symbol = "mouse_up"]
+                    Release,
+                }
+            }),
+            "duplicate symbol \"mouse_up\""
+        );
+        assert_eq!(
+            error(&parse_quote! {
+                struct Event;
+            }),
+            "`SymbolEnum` can only be derived for enums"
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use alloc::{borrow::ToOwned, boxed::Box};
+
+/// Allocator for the strings backing dynamically created symbols.
+///
+/// By default, the registry allocates the strings of new symbols on the heap
+/// and leaks them (see [`LeakingSymbolAllocator`]). A custom allocator can be
+/// installed with [`Registry::set_allocator()`](crate::Registry::set_allocator),
+/// for example to place all interned strings in an arena that the program
+/// controls.
+///
+/// The allocator is only used for symbols created from non-static strings,
+/// i.e. through [`Symbol::new()`](crate::Symbol::new) and
+/// [`Registry::get_or_insert()`](crate::Registry::get_or_insert). Symbols
+/// created through `sym!()` or [`Symbol::new_static()`](crate::Symbol::new_static)
+/// reuse the static string directly.
+///
+/// The allocator is always called while holding the registry's write lock.
+pub trait GlobalSymbolAllocator: Sync {
+    /// Allocate a copy of `string`.
+    ///
+    /// The returned string must be equal to `string`. Symbols referring to it
+    /// may be used at any point later in the program, so reclaiming the memory
+    /// is only sound when it is known that no such symbols are in use anymore
+    /// (e.g. at shutdown).
+    fn alloc_str(&self, string: &str) -> &'static str;
+
+    /// Allocate the reference to an interned string, which is what a
+    /// [`Symbol`](crate::Symbol) points to.
+    ///
+    /// The default implementation allocates the reference on the heap and leaks
+    /// it.
+    fn alloc_str_ref(&self, string: &'static str) -> &'static &'static str {
+        Box::leak(Box::new(string))
+    }
+}
+
+/// The default [`GlobalSymbolAllocator`], which allocates on the heap and
+/// never frees anything.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LeakingSymbolAllocator;
+
+impl GlobalSymbolAllocator for LeakingSymbolAllocator {
+    #[inline]
+    fn alloc_str(&self, string: &str) -> &'static str {
+        string.to_owned().leak()
+    }
+}
@@ -0,0 +1,106 @@
+use core::{
+    cell::UnsafeCell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Fixed-size buffer for the strings of symbols created at runtime, without
+/// using the heap.
+///
+/// This is used with [`Symbol::new_in_arena()`](crate::Symbol::new_in_arena)
+/// to intern runtime strings when the `alloc` feature is disabled, or to put a
+/// hard bound on the memory that such strings can use. The arena is a bump
+/// allocator: space is never reused, and once the arena is full, no more
+/// symbols can be created in it.
+///
+/// Each new symbol takes the length of its string, plus a reference to the
+/// string (16 bytes on 64-bit targets), plus up to 7 bytes of padding.
+///
+/// ```rust,ignore
+/// static ARENA: StaticArena<4096> = StaticArena::new();
+///
+/// let symbol = Symbol::new_in_arena(&ARENA, name).expect("symbol arena is full");
+/// ```
+#[repr(C)]
+pub struct StaticArena<const N: usize> {
+    /// Aligns `buf` for the `&'static str` references stored in it.
+    _align: [&'static str; 0],
+    buf: UnsafeCell<[u8; N]>,
+    used: AtomicUsize,
+}
+
+// SAFETY: Each range of the buffer is only written once, by the thread that
+// claimed it through `used`, before any reference to it is handed out.
+unsafe impl<const N: usize> Sync for StaticArena<N> {}
+
+impl<const N: usize> StaticArena<N> {
+    /// Create an empty arena.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            _align: [],
+            buf: UnsafeCell::new([0; N]),
+            used: AtomicUsize::new(0),
+        }
+    }
+
+    /// The size of the arena in bytes.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// The number of bytes used by the symbols in the arena so far, including
+    /// references and padding.
+    #[inline]
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// Copy `string` into the arena, along with a reference to it, or return
+    /// `None` if there is not enough room left.
+    pub(crate) fn alloc_str(&'static self, string: &str) -> Option<&'static &'static str> {
+        const REF_SIZE: usize = size_of::<&str>();
+        let mut start = 0;
+        self.used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                start = used.next_multiple_of(align_of::<&str>());
+                let end = start.checked_add(REF_SIZE)?.checked_add(string.len())?;
+                (end <= N).then_some(end)
+            })
+            .ok()?;
+
+        unsafe {
+            // SAFETY: The range `start..end` was claimed above, so nothing else
+            // refers to it, and `start` is aligned for `&str` because the
+            // buffer is.
+            let base = self.buf.get().cast::<u8>();
+            let bytes = base.add(start + REF_SIZE);
+            core::ptr::copy_nonoverlapping(string.as_ptr(), bytes, string.len());
+            let stored: &'static str =
+                core::str::from_utf8_unchecked(core::slice::from_raw_parts(bytes, string.len()));
+            #[allow(clippy::cast_ptr_alignment)] // See above.
+            let slot = base.add(start).cast::<&'static str>();
+            slot.write(stored);
+            Some(&*slot)
+        }
+    }
+}
+
+impl<const N: usize> Default for StaticArena<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for StaticArena<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StaticArena")
+            .field("capacity", &N)
+            .field("used", &self.used())
+            .finish()
+    }
+}
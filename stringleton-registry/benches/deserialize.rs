@@ -0,0 +1,34 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use serde::de::DeserializeSeed;
+use std::hint::black_box;
+use stringleton_registry::{Registry, Symbol};
+
+fn symbols_json(n: usize) -> String {
+    let strings: Vec<String> = (0..n).map(|i| format!("bench_symbol_{i}")).collect();
+    serde_json::to_string(&strings).unwrap()
+}
+
+fn deserialize(c: &mut Criterion) {
+    let json = symbols_json(10_000);
+    let mut group = c.benchmark_group("deserialize 10k symbols");
+    group.bench_function("Vec<Symbol>", |b| {
+        b.iter(|| {
+            let symbols: Vec<Symbol> = serde_json::from_str(black_box(&json)).unwrap();
+            symbols
+        });
+    });
+    group.bench_function("BatchInterner::seq", |b| {
+        b.iter(|| {
+            let mut de = serde_json::Deserializer::from_str(black_box(&json));
+            Registry::global()
+                .batch()
+                .seq()
+                .deserialize(&mut de)
+                .unwrap()
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, deserialize);
+criterion_main!(benches);
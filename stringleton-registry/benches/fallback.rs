@@ -0,0 +1,20 @@
+//! The fallback path of `sym!()` on targets without static constructors (Miri
+//! and `wasm32`), measured on the host. Requires the `test-util` feature.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use stringleton_registry::{Site, Symbol};
+
+fn fallback(c: &mut Criterion) {
+    // Not registered by a static ctor, so the first access interns the symbol.
+    static SITE: Site = Site::new(&"bench_fallback");
+    let get = || unsafe {
+        // SAFETY: The site is only accessed through this function.
+        *SITE.get_ref_without_ctor()
+    };
+    assert_eq!(get(), Symbol::new_static(&"bench_fallback"));
+
+    c.bench_function("sym!() fallback path", |b| b.iter(get));
+}
+
+criterion_group!(benches, fallback);
+criterion_main!(benches);
@@ -0,0 +1,33 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::{collections::HashMap, hint::black_box};
+use stringleton_registry::{Symbol, SymbolMap};
+
+fn lookup(c: &mut Criterion) {
+    let symbols: Vec<Symbol> = (0..1000)
+        .map(|i| Symbol::new(format!("bench_hasher_{i}")))
+        .collect();
+    let default_map: HashMap<Symbol, usize> = symbols.iter().copied().zip(0..).collect();
+    let symbol_map: SymbolMap<usize> = symbols.iter().copied().zip(0..).collect();
+
+    let mut group = c.benchmark_group("lookup 1000 symbols");
+    group.bench_function("HashMap (SipHash)", |b| {
+        b.iter(|| {
+            symbols
+                .iter()
+                .map(|symbol| default_map[black_box(symbol)])
+                .sum::<usize>()
+        });
+    });
+    group.bench_function("SymbolMap", |b| {
+        b.iter(|| {
+            symbols
+                .iter()
+                .map(|symbol| symbol_map[black_box(symbol)])
+                .sum::<usize>()
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, lookup);
+criterion_main!(benches);
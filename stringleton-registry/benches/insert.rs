@@ -0,0 +1,50 @@
+use criterion::{BatchSize, Criterion, Throughput, criterion_group, criterion_main};
+use std::collections::HashSet;
+use stringleton_registry::Registry;
+
+const N: usize = 10_000;
+
+fn insert(c: &mut Criterion) {
+    // Every iteration needs strings that have not been interned before.
+    let mut round = 0;
+    let mut novel_strings = move || {
+        round += 1;
+        (0..N)
+            .map(|i| format!("bench_insert_{round}_{i}"))
+            .collect::<Vec<String>>()
+    };
+
+    let mut group = c.benchmark_group("insert 10k novel symbols");
+    group.throughput(Throughput::Elements(N as u64));
+    group.bench_function("Registry::batch", |b| {
+        b.iter_batched(
+            &mut novel_strings,
+            |strings| {
+                let mut batch = Registry::global().batch();
+                for string in &strings {
+                    _ = batch.intern(string);
+                }
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    // Baseline: The same strings in a `HashSet` with the standard library's
+    // default hasher (SipHash).
+    group.bench_function("HashSet<Box<str>> (SipHash)", |b| {
+        b.iter_batched(
+            &mut novel_strings,
+            |strings| {
+                let mut set = HashSet::new();
+                for string in &strings {
+                    set.insert(Box::<str>::from(string.as_str()));
+                }
+                set
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, insert);
+criterion_main!(benches);
@@ -0,0 +1,39 @@
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use stringleton_registry::{Registry, Symbol};
+
+fn intern(c: &mut Criterion) {
+    static EXISTING: &str = "bench_intern_existing";
+    let existing = Symbol::new_static(&EXISTING);
+
+    let mut group = c.benchmark_group("intern one symbol");
+    group.bench_function("Symbol::new (hit)", |b| {
+        b.iter(|| Symbol::new(black_box(EXISTING)));
+    });
+    let mut round = 0;
+    group.bench_function("Symbol::new (miss)", |b| {
+        b.iter_batched(
+            || {
+                round += 1;
+                format!("bench_intern_miss_{round}")
+            },
+            |string| Symbol::new(&string),
+            BatchSize::SmallInput,
+        );
+    });
+    group.bench_function("Registry::get_or_insert_static (hit)", |b| {
+        b.iter(|| Registry::global().get_or_insert_static(black_box(&EXISTING)));
+    });
+    group.bench_function("Symbol::get (hit)", |b| {
+        b.iter(|| Symbol::get(black_box(EXISTING)));
+    });
+    group.bench_function("Symbol::get (miss)", |b| {
+        b.iter(|| Symbol::get(black_box("bench_intern_missing")));
+    });
+    group.finish();
+
+    assert_eq!(Symbol::new(EXISTING), existing);
+}
+
+criterion_group!(benches, intern);
+criterion_main!(benches);
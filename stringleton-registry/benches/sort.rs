@@ -0,0 +1,35 @@
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use stringleton_registry::Symbol;
+
+fn sort(c: &mut Criterion) {
+    let symbols: Vec<Symbol> = (0..100_000)
+        .map(|i| Symbol::new(format!("bench_sort_{}", (i * 7919) % 100_000)))
+        .collect();
+
+    let mut group = c.benchmark_group("sort 100000 symbols");
+    group.bench_function("sort_by_key", |b| {
+        b.iter_batched_ref(
+            || symbols.clone(),
+            |symbols| symbols.sort_by_key(Symbol::as_str),
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("sort_unstable_by_key", |b| {
+        b.iter_batched_ref(
+            || symbols.clone(),
+            |symbols| symbols.sort_unstable_by_key(Symbol::as_str),
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("sort_lexically", |b| {
+        b.iter_batched_ref(
+            || symbols.clone(),
+            |symbols| Symbol::sort_lexically(symbols),
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
+criterion_group!(benches, sort);
+criterion_main!(benches);
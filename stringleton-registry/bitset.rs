@@ -0,0 +1,200 @@
+use alloc::vec::Vec;
+
+use crate::Symbol;
+
+/// Compact set of symbols, stored as a bitmap indexed by [`Symbol::id()`].
+///
+/// Each symbol in the set takes a single bit, so this is much smaller than a
+/// `HashSet<Symbol>` when the set draws from a bounded vocabulary of symbols,
+/// like the tags of an entity. The bitmap grows to fit the highest ID in the
+/// set, so it is only compact when the symbols have small IDs, i.e., they were
+/// registered early (as all `sym!()` symbols are). An exception is
+/// [`Registry::import_table()`](crate::Registry::import_table), which gives
+/// the symbols of the table the lowest IDs, and moves all other symbols
+/// (including `sym!()` symbols that are not in the table) after them.
+///
+/// Looking up the ID of a symbol takes a global read lock and a hash lookup,
+/// so [`insert()`](Self::insert), [`remove()`](Self::remove), and
+/// [`contains()`](Self::contains) each take the lock once. Operations on IDs
+/// (and [`len()`](Self::len)) do not, so for frequent membership tests, look
+/// up the IDs once with [`Symbol::ids_of()`] and use
+/// [`contains_id()`](Self::contains_id). IDs never change once they have been
+/// observed, so they can be cached for the lifetime of the process:
+///
+/// ```rust
+/// # use stringleton_registry::{Symbol, SymbolBitSet};
+/// let [red, green] = Symbol::ids_of([Symbol::new("red"), Symbol::new("green")]);
+///
+/// let mut colors = SymbolBitSet::new();
+/// colors.insert_id(red);
+/// assert!(colors.contains_id(red));
+/// assert!(!colors.contains_id(green));
+/// ```
+///
+/// Symbols are iterated in the order of their IDs, i.e., the order they were
+/// registered, which is neither lexical nor stable between runs.
+#[derive(Clone, Default)]
+pub struct SymbolBitSet {
+    words: Vec<u64>,
+}
+
+impl SymbolBitSet {
+    /// Create an empty set.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { words: Vec::new() }
+    }
+
+    /// Add `symbol` to the set, returning `true` if it was not already present.
+    ///
+    /// # Panics
+    ///
+    /// See [`Symbol::id()`].
+    #[inline]
+    pub fn insert(&mut self, symbol: Symbol) -> bool {
+        self.insert_id(symbol.id())
+    }
+
+    /// Remove `symbol` from the set, returning `true` if it was present.
+    ///
+    /// # Panics
+    ///
+    /// See [`Symbol::id()`].
+    #[inline]
+    pub fn remove(&mut self, symbol: Symbol) -> bool {
+        self.remove_id(symbol.id())
+    }
+
+    /// Check if `symbol` is in the set.
+    ///
+    /// # Panics
+    ///
+    /// See [`Symbol::id()`].
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, symbol: Symbol) -> bool {
+        self.contains_id(symbol.id())
+    }
+
+    /// Add the symbol with the ID `id` to the set, returning `true` if it was
+    /// not already present.
+    pub fn insert_id(&mut self, id: u32) -> bool {
+        let (word, bit) = split(id);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let present = self.words[word] & bit != 0;
+        self.words[word] |= bit;
+        !present
+    }
+
+    /// Remove the symbol with the ID `id` from the set, returning `true` if it
+    /// was present.
+    pub fn remove_id(&mut self, id: u32) -> bool {
+        let (word, bit) = split(id);
+        let Some(w) = self.words.get_mut(word) else {
+            return false;
+        };
+        let present = *w & bit != 0;
+        *w &= !bit;
+        present
+    }
+
+    /// Check if the symbol with the ID `id` is in the set.
+    #[inline]
+    #[must_use]
+    pub fn contains_id(&self, id: u32) -> bool {
+        let (word, bit) = split(id);
+        self.words.get(word).is_some_and(|w| w & bit != 0)
+    }
+
+    /// The number of symbols in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Check if the set is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Remove all symbols from the set, keeping the allocated memory.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    /// Iterate over the IDs of the symbols in the set, in ascending order.
+    pub fn ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.words.iter().zip(0u32..).flat_map(|(&word, index)| {
+            let mut word = word;
+            core::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros();
+                word &= word - 1;
+                Some(index * 64 + bit)
+            })
+        })
+    }
+
+    /// Iterate over the symbols in the set, in the order of their IDs.
+    ///
+    /// Each symbol is looked up with [`Symbol::from_id()`], which takes a
+    /// global read lock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an ID that was added with [`insert_id()`](Self::insert_id)
+    /// does not belong to a symbol.
+    pub fn iter(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.ids()
+            .map(|id| Symbol::from_id(id).expect("symbol ID is not registered"))
+    }
+}
+
+/// Index of the word and the mask of the bit for `id`.
+#[inline]
+fn split(id: u32) -> (usize, u64) {
+    ((id / 64) as usize, 1 << (id % 64))
+}
+
+impl PartialEq for SymbolBitSet {
+    fn eq(&self, other: &Self) -> bool {
+        // Trailing zero words don't matter.
+        let (short, long) = if self.words.len() <= other.words.len() {
+            (&self.words, &other.words)
+        } else {
+            (&other.words, &self.words)
+        };
+        long[..short.len()] == short[..] && long[short.len()..].iter().all(|&w| w == 0)
+    }
+}
+
+impl Eq for SymbolBitSet {}
+
+impl Extend<Symbol> for SymbolBitSet {
+    fn extend<T: IntoIterator<Item = Symbol>>(&mut self, iter: T) {
+        for symbol in iter {
+            self.insert(symbol);
+        }
+    }
+}
+
+impl FromIterator<Symbol> for SymbolBitSet {
+    fn from_iter<T: IntoIterator<Item = Symbol>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl core::fmt::Debug for SymbolBitSet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,108 @@
+use alloc::string::String;
+
+use crate::Symbol;
+
+/// Builder for symbols made of several segments, like `namespace:sub:name`.
+///
+/// The segments are appended to a single string buffer, which is only
+/// interned by [`finish()`](Self::finish), so building a key incrementally
+/// (for example in a loop) does not create intermediate symbols, unlike
+/// repeated calls to [`Symbol::child()`].
+///
+/// ```rust,ignore
+/// let mut builder = SymbolBuilder::new();
+/// builder.sep(':').push("app").push_sym(sym!(ui)).push("button");
+/// assert_eq!(builder.finish(), sym!("app:ui:button"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct SymbolBuilder {
+    buf: String,
+    sep: Option<char>,
+    empty: bool,
+}
+
+impl SymbolBuilder {
+    /// Create an empty builder without a separator.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buf: String::new(),
+            sep: None,
+            empty: true,
+        }
+    }
+
+    /// Create an empty builder with room for `capacity` bytes.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: String::with_capacity(capacity),
+            ..Self::new()
+        }
+    }
+
+    /// Insert `sep` between this and all following segments.
+    ///
+    /// The separator is not inserted before the first segment. Segments that
+    /// were pushed before calling this are not affected.
+    #[inline]
+    pub fn sep(&mut self, sep: char) -> &mut Self {
+        self.sep = Some(sep);
+        self
+    }
+
+    /// Append the segment `segment`.
+    pub fn push(&mut self, segment: &str) -> &mut Self {
+        if !self.empty
+            && let Some(sep) = self.sep
+        {
+            self.buf.push(sep);
+        }
+        self.buf.push_str(segment);
+        self.empty = false;
+        self
+    }
+
+    /// Append the string of `symbol` as a segment.
+    #[inline]
+    pub fn push_sym(&mut self, symbol: Symbol) -> &mut Self {
+        self.push(symbol.as_str())
+    }
+
+    /// The string built so far.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Intern the string built so far, and reset the builder, keeping the
+    /// separator.
+    ///
+    /// The string is not copied into a separate allocation: if the symbol is
+    /// new, the buffer of the builder becomes the storage of the symbol (see
+    /// [`Symbol::new_take()`]), and the builder starts over with a new buffer.
+    /// The buffer is shrunk to fit first, which may reallocate it, so use
+    /// [`with_capacity()`](Self::with_capacity) with the exact length to
+    /// avoid that. If the symbol already exists, the buffer is kept for the
+    /// next symbol, so building existing symbols in a loop does not allocate
+    /// once the buffer is large enough.
+    ///
+    /// This has the same memory leak caveats as [`Symbol::new()`].
+    #[must_use]
+    pub fn finish(&mut self) -> Symbol {
+        let symbol = Symbol::new_take(&mut self.buf);
+        self.buf.clear();
+        self.empty = true;
+        symbol
+    }
+}
+
+impl Default for SymbolBuilder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,115 @@
+use core::cmp::Ordering;
+
+use crate::Symbol;
+
+/// Symbol wrapper that is ordered by string.
+///
+/// The `Ord` implementation of `Symbol` compares pointer values, which is
+/// cheap, but not meaningful. `ByStr` compares the strings of the symbols
+/// lexically (like `str`), so it can be used in ordered collections like
+/// `BTreeSet<ByStr>` or `BinaryHeap<ByStr>` to get alphabetical order.
+///
+/// Equality is still a pointer comparison, which is equivalent to comparing
+/// the strings, and hashing is the same as for `Symbol`.
+///
+/// Created by [`Symbol::by_str()`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ByStr(pub Symbol);
+
+impl ByStr {
+    /// Get the wrapped symbol.
+    #[inline]
+    #[must_use]
+    pub fn into_symbol(self) -> Symbol {
+        self.0
+    }
+}
+
+impl Symbol {
+    /// Wrap this symbol in [`ByStr`], which is ordered by string.
+    #[inline]
+    #[must_use]
+    pub fn by_str(self) -> ByStr {
+        ByStr(self)
+    }
+
+    /// Sort a slice of symbols lexically by their strings.
+    ///
+    /// This is equivalent to `symbols.sort_by_key(|symbol| symbol.as_str())`,
+    /// but the string of each symbol is only looked up once: The symbols are
+    /// copied to a temporary buffer together with their strings, sorted there,
+    /// and copied back. Comparisons then do not need to go through the
+    /// symbol's pointer, saving an indirection per comparison in large sorts.
+    /// For small slices, `sort_by_key()` or sorting a slice of [`ByStr`]
+    /// avoids the allocation.
+    ///
+    /// The default `Ord` implementation of `Symbol` is unaffected.
+    #[cfg(feature = "alloc")]
+    pub fn sort_lexically(symbols: &mut [Symbol]) {
+        let mut decorated: alloc::vec::Vec<(&'static str, Symbol)> = symbols
+            .iter()
+            .map(|&symbol| (symbol.as_str(), symbol))
+            .collect();
+        // Equal strings are the same symbol, so stability does not matter.
+        decorated.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        for (slot, (_, symbol)) in symbols.iter_mut().zip(decorated) {
+            *slot = symbol;
+        }
+    }
+}
+
+impl PartialOrd for ByStr {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ByStr {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.0 == other.0 {
+            Ordering::Equal
+        } else {
+            self.0.as_str().cmp(other.0.as_str())
+        }
+    }
+}
+
+impl core::ops::Deref for ByStr {
+    type Target = Symbol;
+
+    #[inline]
+    fn deref(&self) -> &Symbol {
+        &self.0
+    }
+}
+
+impl From<Symbol> for ByStr {
+    #[inline]
+    fn from(value: Symbol) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ByStr> for Symbol {
+    #[inline]
+    fn from(value: ByStr) -> Self {
+        value.0
+    }
+}
+
+impl core::fmt::Debug for ByStr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl core::fmt::Display for ByStr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
@@ -0,0 +1,61 @@
+//! C API for the global symbol registry (`capi` feature).
+//!
+//! The declarations of these functions are in `stringleton.h`, next to this
+//! file. The header is maintained by hand, and a test checks that it matches
+//! the signatures here.
+
+use crate::{Symbol, SymbolFfi};
+
+/// Get the string of the symbol represented by `handle`.
+///
+/// Returns a pointer to the UTF-8 contents of the string, which is not
+/// nul-terminated, and writes its length to `len` (if `len` is not null). The
+/// string lives for the rest of the program.
+///
+/// If `handle` does not represent a registered symbol, this returns null and
+/// does not write to `len`.
+///
+/// # Safety
+///
+/// `len` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn stringleton_symbol_as_str(
+    handle: SymbolFfi,
+    len: *mut usize,
+) -> *const u8 {
+    let Some(symbol) = handle.to_symbol() else {
+        return core::ptr::null();
+    };
+    if !len.is_null() {
+        unsafe {
+            // SAFETY: Precondition.
+            len.write(symbol.len());
+        }
+    }
+    symbol.as_str().as_ptr()
+}
+
+/// Get the symbol for the UTF-8 string at `ptr` with length `len`, creating it
+/// if necessary.
+///
+/// Returns a handle with value 0 if the string is not valid UTF-8. The string
+/// is copied if a new symbol is created.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of `len` bytes. It may be null if `len` is 0.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn stringleton_symbol_from_str(ptr: *const u8, len: usize) -> SymbolFfi {
+    let bytes = if len == 0 {
+        &[]
+    } else {
+        unsafe {
+            // SAFETY: Precondition.
+            core::slice::from_raw_parts(ptr, len)
+        }
+    };
+    match core::str::from_utf8(bytes) {
+        Ok(string) => Symbol::new(string).into(),
+        Err(_) => SymbolFfi(0),
+    }
+}
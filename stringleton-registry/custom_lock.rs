@@ -0,0 +1,208 @@
+use core::{
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A user-provided reader-writer lock protecting the global registry.
+///
+/// This is used when the `custom-lock` feature is enabled, in place of the
+/// `std` or `spin` locks (and without needing `critical-section` for
+/// initialization). The implementation is provided with the
+/// [`custom_registry_lock!()`](crate::custom_registry_lock) macro, which must
+/// be invoked exactly once in the final binary.
+///
+/// Implementations that only have an exclusive lock (e.g. a mutex provided by
+/// an RTOS) can implement the shared methods in terms of the exclusive ones.
+///
+/// # Safety
+///
+/// Implementations must provide the usual reader-writer lock guarantees: while
+/// the exclusive lock is held, no other shared or exclusive lock may be
+/// acquired, and while any shared lock is held, the exclusive lock may not be
+/// acquired. Locking must establish a happens-before relationship with the
+/// previous unlock.
+pub unsafe trait RawRegistryLock: Sync {
+    /// Acquire a shared lock, blocking until it is available.
+    fn lock_shared(&self);
+
+    /// Release a shared lock.
+    ///
+    /// # Safety
+    ///
+    /// The calling thread must hold a shared lock.
+    unsafe fn unlock_shared(&self);
+
+    /// Acquire the exclusive lock, blocking until it is available.
+    fn lock_exclusive(&self);
+
+    /// Release the exclusive lock.
+    ///
+    /// # Safety
+    ///
+    /// The calling thread must hold the exclusive lock.
+    unsafe fn unlock_exclusive(&self);
+}
+
+/// Provide the lock used by the global registry when the `custom-lock`
+/// feature is enabled.
+///
+/// The argument must be a path to a `static` implementing
+/// [`RawRegistryLock`]. This macro must be invoked exactly once in the final
+/// binary, similar to `critical_section::set_impl!()`.
+///
+/// ```rust,ignore
+/// struct MyLock { /* ... */ }
+/// unsafe impl stringleton::RawRegistryLock for MyLock { /* ... */ }
+///
+/// static LOCK: MyLock = MyLock { /* ... */ };
+/// stringleton::custom_registry_lock!(LOCK);
+/// ```
+#[macro_export]
+macro_rules! custom_registry_lock {
+    ($lock:path) => {
+        #[doc(hidden)]
+        #[unsafe(no_mangle)]
+        fn _stringleton_registry_lock() -> &'static dyn $crate::RawRegistryLock {
+            &$lock
+        }
+    };
+}
+
+unsafe extern "Rust" {
+    fn _stringleton_registry_lock() -> &'static dyn RawRegistryLock;
+}
+
+#[inline]
+fn raw_lock() -> &'static dyn RawRegistryLock {
+    unsafe {
+        // SAFETY: This is defined by `custom_registry_lock!()`, with this
+        // exact signature.
+        _stringleton_registry_lock()
+    }
+}
+
+/// Reader-writer lock built on the user-provided [`RawRegistryLock`].
+///
+/// The protected value is created lazily, so this can be used in a `static`
+/// without a `OnceLock`.
+pub(crate) struct RwLock<T> {
+    initialized: AtomicBool,
+    data: UnsafeCell<Option<T>>,
+}
+
+// SAFETY: Access to `data` is synchronized by the raw lock.
+unsafe impl<T: Send + Sync> Sync for RwLock<T> {}
+
+impl<T: Default> RwLock<T> {
+    pub const fn new() -> Self {
+        Self {
+            initialized: AtomicBool::new(false),
+            data: UnsafeCell::new(None),
+        }
+    }
+
+    fn ensure_initialized(&self, lock: &dyn RawRegistryLock) {
+        if self.initialized.load(Ordering::Acquire) {
+            return;
+        }
+        lock.lock_exclusive();
+        unsafe {
+            // SAFETY: We hold the exclusive lock.
+            let data = &mut *self.data.get();
+            if data.is_none() {
+                *data = Some(T::default());
+            }
+            self.initialized.store(true, Ordering::Release);
+            lock.unlock_exclusive();
+        }
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        let lock = raw_lock();
+        self.ensure_initialized(lock);
+        lock.lock_shared();
+        RwLockReadGuard { rwlock: self, lock }
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+        let lock = raw_lock();
+        self.ensure_initialized(lock);
+        lock.lock_exclusive();
+        RwLockWriteGuard { rwlock: self, lock }
+    }
+
+    /// # Safety
+    ///
+    /// Must hold the lock, after `ensure_initialized()`.
+    unsafe fn get(&self) -> *mut T {
+        unsafe {
+            // SAFETY: Initialized before any guard is created.
+            (*self.data.get()).as_mut().unwrap_unchecked()
+        }
+    }
+}
+
+pub(crate) struct RwLockReadGuard<'a, T: Default> {
+    rwlock: &'a RwLock<T>,
+    lock: &'static dyn RawRegistryLock,
+}
+
+impl<T: Default> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe {
+            // SAFETY: We hold a shared lock.
+            &*self.rwlock.get()
+        }
+    }
+}
+
+impl<T: Default> Drop for RwLockReadGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: The shared lock was acquired in `RwLock::read()`.
+            self.lock.unlock_shared();
+        }
+    }
+}
+
+pub(crate) struct RwLockWriteGuard<'a, T: Default> {
+    rwlock: &'a RwLock<T>,
+    lock: &'static dyn RawRegistryLock,
+}
+
+impl<T: Default> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe {
+            // SAFETY: We hold the exclusive lock.
+            &*self.rwlock.get()
+        }
+    }
+}
+
+impl<T: Default> DerefMut for RwLockWriteGuard<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe {
+            // SAFETY: We hold the exclusive lock.
+            &mut *self.rwlock.get()
+        }
+    }
+}
+
+impl<T: Default> Drop for RwLockWriteGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            // SAFETY: The exclusive lock was acquired in `RwLock::write()`.
+            self.lock.unlock_exclusive();
+        }
+    }
+}
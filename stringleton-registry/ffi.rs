@@ -0,0 +1,79 @@
+use crate::Symbol;
+
+/// FFI-friendly handle for a [`Symbol`].
+///
+/// This is a plain `u64` produced by [`Symbol::to_ffi()`], and is suitable for
+/// storing symbols in buffers that are shared with foreign code (e.g., GPU
+/// buffers). When the `bytemuck` feature is enabled, this type implements
+/// `bytemuck::Pod` and `bytemuck::Zeroable`, so slices of handles can be cast
+/// to bytes safely.
+///
+/// A handle is only meaningful within the process that produced it. Converting
+/// a handle back to a [`Symbol`] checks that it refers to a registered symbol,
+/// taking a global read-lock.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[repr(transparent)]
+pub struct SymbolFfi(pub u64);
+
+impl SymbolFfi {
+    /// Convert the handle back to a symbol, checking that it is valid.
+    ///
+    /// This is equivalent to [`Symbol::try_from_ffi()`].
+    #[inline]
+    #[must_use]
+    pub fn to_symbol(self) -> Option<Symbol> {
+        Symbol::try_from_ffi(self.0)
+    }
+}
+
+impl From<Symbol> for SymbolFfi {
+    #[inline]
+    fn from(value: Symbol) -> Self {
+        Self(value.to_ffi())
+    }
+}
+
+impl TryFrom<SymbolFfi> for Symbol {
+    type Error = InvalidSymbolFfi;
+
+    #[inline]
+    fn try_from(value: SymbolFfi) -> Result<Self, Self::Error> {
+        value.to_symbol().ok_or(InvalidSymbolFfi(value))
+    }
+}
+
+impl core::fmt::Debug for SymbolFfi {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "SymbolFfi({:#x})", self.0)
+    }
+}
+
+/// Error returned when a [`SymbolFfi`] handle does not refer to a registered
+/// symbol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidSymbolFfi(pub SymbolFfi);
+
+impl core::fmt::Display for InvalidSymbolFfi {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid symbol handle: {:#x}", (self.0).0)
+    }
+}
+
+impl core::error::Error for InvalidSymbolFfi {}
+
+/// Formatter returned by [`Symbol::ffi_debug()`].
+pub(crate) struct FfiDebug(pub Symbol);
+
+impl core::fmt::Display for FfiDebug {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "sym#{:x}({:?})", self.0.to_ffi(), self.0.as_str())
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+const _: () = {
+    // SAFETY: `SymbolFfi` is a `repr(transparent)` wrapper around `u64`, and
+    // any bit pattern is a valid (if not necessarily registered) handle.
+    unsafe impl bytemuck::Zeroable for SymbolFfi {}
+    unsafe impl bytemuck::Pod for SymbolFfi {}
+};
@@ -0,0 +1,83 @@
+use core::hash::{BuildHasherDefault, Hasher};
+
+/// Fast hasher for symbol keys.
+///
+/// Symbols hash by their pointer value (unless the `stable-hash` feature is
+/// enabled), which is already unique, so running a general-purpose hasher like
+/// `SipHash` over it is wasted effort. This hasher discards the alignment bits of
+/// the pointer and multiplies it by a large odd constant, which carries every
+/// bit of the pointer into the high bits of the product. `finish()` rotates
+/// those high bits into the low bits, which `hashbrown`-style tables use to
+/// pick a bucket.
+///
+/// Other values (like strings, when `stable-hash` is enabled) are hashed with
+/// a simple multiplicative hash, similar to `FxHash`. This hasher is not
+/// resistant to hash flooding, which is fine for symbol keys, because symbols should
+/// not be created from untrusted input in the first place.
+///
+/// Use [`SymbolMap`] or [`BuildSymbolHasher`] to use this hasher in a map.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct SymbolHasher {
+    hash: u64,
+}
+
+/// `BuildHasher` for [`SymbolHasher`].
+pub type BuildSymbolHasher = BuildHasherDefault<SymbolHasher>;
+
+/// `HashMap` with symbol keys, using [`SymbolHasher`].
+#[cfg(feature = "std")]
+pub type SymbolMap<V> = std::collections::HashMap<crate::Symbol, V, BuildSymbolHasher>;
+
+/// Concurrent `DashMap` with symbol keys, using [`SymbolHasher`].
+#[cfg(feature = "dashmap")]
+pub type ConcurrentSymbolMap<V> = dashmap::DashMap<crate::Symbol, V, BuildSymbolHasher>;
+
+impl SymbolHasher {
+    const SEED: u64 = 0x517c_c1b7_2722_0a95;
+
+    #[inline]
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(Self::SEED);
+    }
+}
+
+impl Hasher for SymbolHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        // The low bits of a product only depend on the low bits of its
+        // factors, so move the well-mixed high bits down.
+        self.hash.rotate_left(26)
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add_to_hash(u64::from_le_bytes(word));
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        // Symbol pointers point to a `&'static str`, so the lowest bits are
+        // always zero due to alignment.
+        const ALIGN_BITS: u32 = align_of::<&'static str>().trailing_zeros();
+        self.add_to_hash((i >> ALIGN_BITS) as u64);
+    }
+}
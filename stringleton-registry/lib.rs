@@ -24,26 +24,122 @@
 
 #![no_std]
 
-#[cfg(feature = "std")]
+// Tests always have `std`, also in `no_std` configurations.
+#[cfg(any(feature = "std", test))]
 extern crate std;
 
-#[cfg(any(feature = "std", feature = "alloc"))]
+// The registry's hash maps always allocate, even without the `alloc` feature.
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
+mod allocator;
+mod arena;
+#[cfg(feature = "alloc")]
+mod bitset;
+#[cfg(feature = "alloc")]
+mod builder;
+mod by_str;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "custom-lock")]
+mod custom_lock;
+mod ffi;
+mod hasher;
+#[cfg(feature = "phf")]
+mod perfect;
 mod registry;
+#[cfg(feature = "alloc")]
+mod scoped;
+#[cfg(feature = "serde")]
+pub mod serde_known;
 mod site;
 mod static_symbol;
+mod str_keyed;
 mod symbol;
+#[cfg(feature = "thread-cache")]
+mod thread_cache;
+#[cfg(feature = "clap")]
+mod value_parser;
 
+#[cfg(feature = "alloc")]
+pub use allocator::*;
+pub use arena::*;
+#[cfg(feature = "alloc")]
+pub use bitset::*;
+#[cfg(feature = "alloc")]
+pub use builder::*;
+pub use by_str::*;
+#[cfg(feature = "custom-lock")]
+pub use custom_lock::RawRegistryLock;
+pub use ffi::*;
+pub use hasher::*;
+#[cfg(feature = "phf")]
+pub use perfect::*;
 pub use registry::*;
+#[cfg(feature = "alloc")]
+pub use scoped::*;
 pub use site::*;
 pub use static_symbol::*;
+pub use str_keyed::*;
 pub use symbol::*;
+#[cfg(feature = "clap")]
+pub use value_parser::*;
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// A registry that is separate from the global one, for tests that depend
+    /// on its exact contents or change its configuration.
+    #[cfg(all(feature = "alloc", not(feature = "custom-lock")))]
+    fn local_registry() -> &'static Registry {
+        std::boxed::Box::leak(std::boxed::Box::new(Registry::new()))
+    }
+
+    #[cfg(feature = "custom-lock")]
+    mod test_lock {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Simple spinning reader-writer lock for testing `custom-lock`.
+        struct TestLock(AtomicUsize);
+        const WRITER: usize = usize::MAX;
+
+        unsafe impl crate::RawRegistryLock for TestLock {
+            fn lock_shared(&self) {
+                while self
+                    .0
+                    .fetch_update(Ordering::Acquire, Ordering::Relaxed, |n| {
+                        (n != WRITER).then_some(n + 1)
+                    })
+                    .is_err()
+                {
+                    core::hint::spin_loop();
+                }
+            }
+
+            unsafe fn unlock_shared(&self) {
+                self.0.fetch_sub(1, Ordering::Release);
+            }
+
+            fn lock_exclusive(&self) {
+                while self
+                    .0
+                    .compare_exchange_weak(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+                    .is_err()
+                {
+                    core::hint::spin_loop();
+                }
+            }
+
+            unsafe fn unlock_exclusive(&self) {
+                self.0.store(0, Ordering::Release);
+            }
+        }
+
+        static LOCK: TestLock = TestLock(AtomicUsize::new(0));
+        crate::custom_registry_lock!(LOCK);
+    }
+
     #[cfg(target_arch = "wasm32")]
     wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
     #[cfg(target_arch = "wasm32")]
@@ -77,6 +173,319 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_static() {
+        static STRING: &str = "get_static";
+        assert_eq!(Symbol::get_static(&STRING), None);
+        let symbol = Symbol::new_static(&STRING);
+        assert_eq!(Symbol::get_static(&STRING), Some(symbol));
+        assert_eq!(Symbol::get_static(&"get_static"), Some(symbol));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn from_boxed_str() {
+        let symbol = Symbol::from_boxed_str("from_boxed_str".into());
+        assert_eq!(symbol, "from_boxed_str");
+        assert_eq!(Symbol::from_boxed_str("from_boxed_str".into()), symbol);
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", not(feature = "custom-lock")))]
+    fn from_boxed_str_no_copy() {
+        // Uses a separate registry, because `from_boxed_str()` interns the same
+        // string in the global one.
+        let registry = local_registry();
+        let boxed: std::boxed::Box<str> = "from_boxed_str".into();
+        let ptr = boxed.as_ptr();
+        let symbol = registry.get_or_insert_boxed(boxed);
+        assert_eq!(symbol, "from_boxed_str");
+        // The box was reused as the symbol's storage.
+        assert_eq!(symbol.as_str().as_ptr(), ptr);
+
+        let again = registry.get_or_insert_boxed("from_boxed_str".into());
+        assert_eq!(again, symbol);
+        assert_eq!(again.as_str().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn new_in_arena() {
+        // Room for two 14-byte strings, each padded to 16 bytes, plus their
+        // references, whatever the pointer width.
+        static ARENA: StaticArena<{ 2 * (size_of::<&str>() + 16) }> = StaticArena::new();
+        let a = Symbol::new_in_arena(&ARENA, "new_in_arena.a").unwrap();
+        assert_eq!(a, "new_in_arena.a");
+        let start = core::ptr::from_ref(&ARENA) as usize;
+        let range = start..start + size_of_val(&ARENA);
+        assert!(range.contains(&(a.as_str().as_ptr() as usize)));
+        let used = ARENA.used();
+        assert_eq!(used, size_of::<&str>() + "new_in_arena.a".len());
+
+        // Existing symbols don't take up space.
+        assert_eq!(Symbol::new_in_arena(&ARENA, "new_in_arena.a"), Some(a));
+        let b = Symbol::new_static(&"new_in_arena.b");
+        assert_eq!(Symbol::new_in_arena(&ARENA, "new_in_arena.b"), Some(b));
+        assert_eq!(ARENA.used(), used);
+
+        // Too long for the remaining space.
+        assert_eq!(
+            Symbol::new_in_arena(&ARENA, "new_in_arena.this_does_not_fit"),
+            None
+        );
+        assert_eq!(Symbol::get("new_in_arena.this_does_not_fit"), None);
+        assert_eq!(ARENA.used(), used);
+        let c = Symbol::new_in_arena(&ARENA, "new_in_arena.c").unwrap();
+        assert_eq!(c, "new_in_arena.c");
+        assert!(range.contains(&(c.as_str().as_ptr() as usize)));
+    }
+
+    #[test]
+    #[cfg(all(feature = "profiling", not(feature = "custom-lock")))]
+    fn access_stats() {
+        let registry = local_registry();
+        assert_eq!(registry.access_stats(), AccessStats::default());
+
+        let a = registry.get_or_insert("access_stats.a");
+        assert_eq!(registry.get_or_insert("access_stats.a"), a);
+        _ = registry.get_or_insert_reporting("access_stats.b");
+        _ = registry.try_get_or_insert("access_stats.a");
+        // Lookups without interning are not counted.
+        _ = registry.get("access_stats.a");
+        let stats = registry.access_stats();
+        assert_eq!(stats.read_hits, 2);
+        assert_eq!(stats.write_inserts, 2);
+
+        registry.reset_access_stats();
+        assert_eq!(registry.access_stats(), AccessStats::default());
+    }
+
+    #[test]
+    #[cfg(feature = "thread-cache")]
+    fn thread_cache() {
+        let a = Symbol::new("thread_cache.a");
+        // Fill the cache, so `a` is evicted.
+        let many: std::vec::Vec<Symbol> = (0..20)
+            .map(|i| Symbol::new(std::format!("thread_cache.{i}")))
+            .collect();
+        assert_eq!(Symbol::new("thread_cache.a"), a);
+        assert_eq!(Symbol::new("thread_cache.a"), a);
+        assert_eq!(Symbol::new("thread_cache.19"), many[19]);
+        assert_eq!(Symbol::new("thread_cache.0"), many[0]);
+        let b = std::thread::spawn(|| Symbol::new("thread_cache.a"))
+            .join()
+            .unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn by_str() {
+        use std::collections::{BTreeSet, BinaryHeap};
+
+        let names = ["by_str.b", "by_str.c", "by_str.a"];
+        let mut heap: BinaryHeap<ByStr> = names.iter().map(|s| Symbol::new(s).by_str()).collect();
+        assert_eq!(
+            heap.pop().map(ByStr::into_symbol),
+            Some(Symbol::new("by_str.c"))
+        );
+        assert_eq!(heap.pop().unwrap(), ByStr(Symbol::new("by_str.b")));
+        assert_eq!(heap.pop().unwrap().as_str(), "by_str.a");
+        assert_eq!(heap.pop(), None);
+
+        let mut heap: BinaryHeap<core::cmp::Reverse<ByStr>> = names
+            .iter()
+            .map(|s| core::cmp::Reverse(Symbol::new(s).by_str()))
+            .collect();
+        assert_eq!(heap.pop().unwrap().0, Symbol::new("by_str.a").by_str());
+
+        let set: BTreeSet<ByStr> = names.iter().map(|s| ByStr::from(Symbol::new(s))).collect();
+        let sorted: std::vec::Vec<&str> = set.iter().map(|s| s.as_str()).collect();
+        assert_eq!(sorted, ["by_str.a", "by_str.b", "by_str.c"]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn str_keyed() {
+        let mut map = std::collections::HashMap::new();
+        map.insert(StrKeyed(Symbol::new("str_keyed.a")), 1);
+        map.insert(StrKeyed::from(Symbol::new("str_keyed.b")), 2);
+        assert_eq!(map.get("str_keyed.a"), Some(&1));
+        assert_eq!(map.get(&StrKeyed(Symbol::new("str_keyed.b"))), Some(&2));
+        assert_eq!(map.get("str_keyed.c"), None);
+        let key = map.keys().find(|k| k.as_str() == "str_keyed.a").unwrap();
+        assert_eq!(key.0, "str_keyed.a");
+        assert_eq!(std::format!("{key}"), "str_keyed.a");
+    }
+
+    #[test]
+    #[cfg(feature = "dashmap")]
+    fn concurrent_symbol_map() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Symbol>();
+
+        let a = Symbol::new("concurrent_map.a");
+        let map = std::sync::Arc::new(ConcurrentSymbolMap::default());
+        let threads: std::vec::Vec<_> = (0..4)
+            .map(|i| {
+                let map = map.clone();
+                std::thread::spawn(move || {
+                    map.insert(Symbol::new(std::format!("concurrent_map.{i}")), i);
+                    *map.entry(a).or_insert(0) += 1;
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert_eq!(map.len(), 5);
+        assert_eq!(*map.get(&a).unwrap(), 4);
+        assert_eq!(*map.get(&Symbol::new("concurrent_map.2")).unwrap(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn symbol_map() {
+        use core::hash::BuildHasher;
+
+        let a = Symbol::new("symbol_map.a");
+        let b = Symbol::new("symbol_map.b");
+        let mut map = SymbolMap::default();
+        map.insert(a, 1);
+        map.insert(b, 2);
+        assert_eq!(map[&a], 1);
+        assert_eq!(map[&Symbol::new("symbol_map.b")], 2);
+        assert_eq!(map.get(&Symbol::new("symbol_map.c")), None);
+
+        let state = BuildSymbolHasher::default();
+        assert_eq!(a.hash_one(&state), state.hash_one(a));
+        assert_ne!(state.hash_one(a), state.hash_one(b));
+        assert_eq!(a.as_ptr_usize() as u64, a.to_ffi());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn eq_owned_strings() {
+        use alloc::{borrow::Cow, string::String};
+
+        let a = Symbol::new_static(&"eq_owned");
+        assert_eq!(a, String::from("eq_owned"));
+        assert_eq!(String::from("eq_owned"), a);
+        assert_ne!(a, String::from("other"));
+        assert_eq!(a, Cow::Borrowed("eq_owned"));
+        assert_eq!(Cow::<str>::Owned(String::from("eq_owned")), a);
+        assert_ne!(Cow::Borrowed("other"), a);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn new_os() {
+        let a = Symbol::new_os(std::ffi::OsStr::new("new_os.txt")).unwrap();
+        assert_eq!(a, Symbol::new("new_os.txt"));
+        let b = Symbol::new_path(std::path::Path::new("new_os.txt")).unwrap();
+        assert_eq!(a, b);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            let invalid = std::ffi::OsStr::from_bytes(b"new_os\xff");
+            assert_eq!(Symbol::new_os(invalid), None);
+            assert_eq!(Symbol::new_path(std::path::Path::new(invalid)), None);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn interned_string() {
+        use alloc::{rc::Rc, string::String};
+
+        /// Mock interned string type from another library.
+        #[derive(PartialEq, Debug)]
+        struct Other(Rc<str>);
+        impl InternedString for Other {
+            fn as_str(&self) -> &str {
+                &self.0
+            }
+            fn from_str_interned(string: &str) -> Self {
+                Other(string.into())
+            }
+        }
+
+        let other = Other::from_str_interned("interned_string");
+        let a = Symbol::convert_from(&other);
+        assert_eq!(a, "interned_string");
+        assert_eq!(a, Symbol::from_str_interned("interned_string"));
+        assert_eq!(Other::from_str_interned(a.as_str()), other);
+        assert_eq!(InternedString::as_str(&a), String::from("interned_string"));
+    }
+
+    #[test]
+    fn eq_ignore_ascii_case() {
+        let help = Symbol::new_static(&"help");
+        assert!(help.eq_ignore_ascii_case("HELP"));
+        assert!(help.eq_ignore_ascii_case("Help"));
+        assert!(!help.eq_ignore_ascii_case("helps"));
+        assert!(Symbol::get("HELP").is_none());
+    }
+
+    #[test]
+    fn words() {
+        fn words(string: &'static &'static str) -> std::vec::Vec<&'static str> {
+            Symbol::new_static(string).words().collect()
+        }
+
+        assert_eq!(words(&"renderShadowMap"), ["render", "Shadow", "Map"]);
+        assert_eq!(words(&"render_shadow_map"), ["render", "shadow", "map"]);
+        assert_eq!(
+            words(&"ui.button-primary_hoverState"),
+            ["ui", "button", "primary", "hover", "State"]
+        );
+        assert_eq!(words(&"RenderPass"), ["Render", "Pass"]);
+        assert_eq!(words(&"parse_HTTPResponse"), ["parse", "HTTP", "Response"]);
+        assert_eq!(words(&"MAX_SIZE"), ["MAX", "SIZE"]);
+        assert_eq!(words(&"vec3Add"), ["vec3", "Add"]);
+        assert_eq!(words(&"__init__"), ["init"]);
+        assert_eq!(words(&"ÉtéHiver"), ["Été", "Hiver"]);
+        assert!(words(&"").is_empty());
+        assert!(words(&"_-.").is_empty());
+
+        let symbol = Symbol::new_static(&"wordsTest");
+        let first = symbol.words().next().unwrap();
+        assert_eq!(first.as_ptr(), symbol.as_str().as_ptr());
+    }
+
+    #[test]
+    fn common_prefix() {
+        let abc = Symbol::new_static(&"common_prefix.abc");
+        let abd = Symbol::new_static(&"common_prefix.abd");
+        let ab = Symbol::new_static(&"common_prefix.ab");
+        let other = Symbol::new_static(&"other");
+        let accented = Symbol::new_static(&"common_prefix.é");
+        let accented2 = Symbol::new_static(&"common_prefix.è");
+
+        assert_eq!(Symbol::common_prefix(&[]), "");
+        assert_eq!(Symbol::common_prefix(&[abc]), "common_prefix.abc");
+        assert_eq!(Symbol::common_prefix(&[abc, abc]), "common_prefix.abc");
+        assert_eq!(Symbol::common_prefix(&[abc, abd]), "common_prefix.ab");
+        assert_eq!(Symbol::common_prefix(&[abc, abd, ab]), "common_prefix.ab");
+        assert_eq!(Symbol::common_prefix(&[ab, abc]), "common_prefix.ab");
+        assert_eq!(Symbol::common_prefix(&[abc, other]), "");
+        assert_eq!(
+            Symbol::common_prefix(&[accented, accented2]),
+            "common_prefix."
+        );
+        assert!(core::ptr::eq(
+            Symbol::common_prefix(&[abc, abd]).as_ptr(),
+            abc.as_str().as_ptr()
+        ));
+    }
+
+    #[test]
+    fn into_static_str() {
+        static STORED: std::sync::OnceLock<&'static str> = std::sync::OnceLock::new();
+        let s: &'static str = Symbol::new_static(&"into_static_str").into();
+        assert_eq!(*STORED.get_or_init(|| s), "into_static_str");
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn address() {
@@ -87,4 +496,1098 @@ mod tests {
         let a3 = Symbol::try_from_ffi(a.to_ffi()).unwrap();
         assert_eq!(a3, a);
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn batch() {
+        let symbols: alloc::vec::Vec<Symbol> = {
+            let mut batch = Registry::global().batch();
+            (0..100)
+                .map(|i| batch.intern(&alloc::format!("batch{i}")))
+                .collect()
+        };
+        assert_eq!(
+            symbols[0],
+            Registry::global().batch().intern_static(&"batch0")
+        );
+        for (i, sym) in symbols.iter().enumerate() {
+            assert_eq!(*sym, Symbol::new(alloc::format!("batch{i}")));
+        }
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "custom-lock")))]
+    #[test]
+    fn custom_allocator() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        struct CountingAllocator(AtomicUsize);
+        impl GlobalSymbolAllocator for CountingAllocator {
+            fn alloc_str(&self, string: &str) -> &'static str {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                LeakingSymbolAllocator.alloc_str(string)
+            }
+        }
+        static ALLOCATOR: CountingAllocator = CountingAllocator(AtomicUsize::new(0));
+
+        let registry = local_registry();
+        assert!(registry.set_allocator(&ALLOCATOR).is_ok());
+        assert!(registry.set_allocator(&ALLOCATOR).is_err());
+
+        let a = registry.get_or_insert("custom_allocator");
+        let a2 = registry.get_or_insert("custom_allocator");
+        assert_eq!(a, a2);
+        assert_eq!(a, "custom_allocator");
+        assert_eq!(ALLOCATOR.0.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", not(feature = "custom-lock")))]
+    fn panic_during_insert() {
+        // The lock is not `RefUnwindSafe` with the `spin` feature.
+        use std::panic::AssertUnwindSafe;
+
+        struct PanickingAllocator;
+        impl GlobalSymbolAllocator for PanickingAllocator {
+            fn alloc_str(&self, string: &str) -> &'static str {
+                assert!(string != "panic_during_insert.bad", "injected panic");
+                LeakingSymbolAllocator.alloc_str(string)
+            }
+        }
+        let registry = local_registry();
+        assert!(registry.set_allocator(&PanickingAllocator).is_ok());
+
+        let good = registry.get_or_insert("panic_during_insert.good");
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            registry.get_or_insert("panic_during_insert.bad")
+        }));
+        assert!(result.is_err());
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            registry.get_or_insert_boxed("panic_during_insert.bad".into())
+        }));
+        assert!(result.is_err());
+
+        // The registry is still consistent, even though the lock is poisoned.
+        let read = registry.read();
+        assert_eq!(read.get("panic_during_insert.bad"), None);
+        assert_eq!(read.len(), 1);
+        assert_eq!(read.iter_ordered().collect::<std::vec::Vec<_>>(), [good]);
+        assert_eq!(read.get_by_address(good.to_ffi()), Some(good));
+        assert_eq!(read.id_of(good), Some(0));
+        drop(read);
+
+        let other = registry.get_or_insert("panic_during_insert.other");
+        assert_eq!(registry.read().id_of(other), Some(1));
+        assert_eq!(registry.read().get_by_id(1), Some(other));
+    }
+
+    #[test]
+    #[cfg(not(feature = "custom-lock"))]
+    fn with_capacity() {
+        static REGISTRY: std::sync::LazyLock<Registry> =
+            std::sync::LazyLock::new(|| Registry::with_capacity(100));
+        assert!(REGISTRY.read().capacity() >= 100);
+    }
+
+    #[test]
+    fn init_global_with_capacity() {
+        let len = Registry::global().read().len();
+        Registry::init_global_with_capacity(len + 1000);
+        assert!(Registry::global().read().capacity() >= len + 1000);
+        // Already has room.
+        Registry::init_global_with_capacity(0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sort_lexically() {
+        let mut symbols = ["sort_c", "sort_a", "sort_b", "sort_a", ""].map(Symbol::new);
+        Symbol::sort_lexically(&mut symbols);
+        assert_eq!(symbols, ["", "sort_a", "sort_a", "sort_b", "sort_c"]);
+        Symbol::sort_lexically(&mut []);
+    }
+
+    #[test]
+    fn new_static_bytes_unchecked() {
+        static BYTES: &[u8] = b"new_static_bytes";
+        static STRING: &str = "new_static_bytes";
+        let symbol = unsafe { Symbol::new_static_bytes_unchecked(&BYTES) };
+        assert_eq!(symbol, "new_static_bytes");
+        assert_eq!(symbol.as_str().as_ptr(), BYTES.as_ptr());
+        assert_eq!(Symbol::new_static(&STRING), symbol);
+    }
+
+    #[test]
+    fn fingerprint() {
+        static EMPTY: &str = "";
+        static A: &str = "a";
+        static FOOBAR: &str = "foobar";
+        // Test vectors for 64-bit FNV-1a.
+        assert_eq!(
+            Symbol::new_static(&EMPTY).fingerprint(),
+            0xcbf2_9ce4_8422_2325
+        );
+        assert_eq!(Symbol::new_static(&A).fingerprint(), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(
+            Symbol::new_static(&FOOBAR).fingerprint(),
+            0x8594_4171_f739_67e8
+        );
+    }
+
+    #[test]
+    fn get_by_ptr() {
+        static STRING: &str = "get_by_ptr";
+        let symbol = Symbol::new_static(&STRING);
+        assert_eq!(
+            Registry::global().get_by_ptr(symbol.as_ptr().as_ptr()),
+            Some(symbol)
+        );
+        assert_eq!(Registry::global().get_by_ptr(core::ptr::null()), None);
+        let unregistered: &'static &'static str = &"get_by_ptr";
+        assert_eq!(Registry::global().get_by_ptr(unregistered), None);
+    }
+
+    /// Everything that only inspects a symbol must work without `alloc`. Run
+    /// with `--no-default-features --features spin,critical-section` to check.
+    #[test]
+    fn no_alloc_surface() {
+        use core::fmt::Write;
+
+        struct Buffer {
+            bytes: [u8; 32],
+            len: usize,
+        }
+
+        impl Write for Buffer {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                let end = self.len + s.len();
+                self.bytes
+                    .get_mut(self.len..end)
+                    .ok_or(core::fmt::Error)?
+                    .copy_from_slice(s.as_bytes());
+                self.len = end;
+                Ok(())
+            }
+        }
+
+        impl Buffer {
+            fn take(&mut self) -> &str {
+                let len = core::mem::take(&mut self.len);
+                core::str::from_utf8(&self.bytes[..len]).unwrap()
+            }
+        }
+
+        let a = Symbol::new_static(&"no_alloc.a");
+        let b = Symbol::new_static(&"no_alloc.b");
+        let empty = Symbol::new_static(&"");
+        assert_eq!(a.as_str(), "no_alloc.a");
+        assert_eq!(a.len(), 10);
+        assert!(!a.is_empty());
+        assert!(empty.is_empty());
+        assert_eq!(a, "no_alloc.a");
+        assert_ne!(a, b);
+        assert!(a < b);
+        assert!(a < "no_alloc.b");
+        assert_eq!(Symbol::get("no_alloc.a"), Some(a));
+
+        let mut buffer = Buffer {
+            bytes: [0; 32],
+            len: 0,
+        };
+        a.write_to(&mut buffer).unwrap();
+        assert_eq!(buffer.take(), "no_alloc.a");
+        write!(buffer, "{a}").unwrap();
+        assert_eq!(buffer.take(), "no_alloc.a");
+        write!(buffer, "{a:?}").unwrap();
+        assert_eq!(buffer.take(), "\"no_alloc.a\"");
+        write!(buffer, "{b:>12}").unwrap();
+        assert_eq!(buffer.take(), "  no_alloc.b");
+        let long = Symbol::new_static(&"no_alloc.longer_than_the_buffer_itself");
+        assert!(long.write_to(&mut buffer).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", not(feature = "custom-lock")))]
+    fn leak_counters() {
+        let registry = local_registry();
+        let reference = size_of::<&str>();
+        assert_eq!((registry.leaked_count(), registry.leaked_bytes()), (0, 0));
+
+        _ = registry.get_or_insert_static(&"leak.static");
+        _ = registry.get_or_insert("leak.static");
+        assert_eq!((registry.leaked_count(), registry.leaked_bytes()), (0, 0));
+
+        _ = registry.get_or_insert("leak.a");
+        _ = registry.get_or_insert("leak.a");
+        assert_eq!(registry.leaked_count(), 1);
+        assert_eq!(registry.leaked_bytes(), 6 + reference);
+
+        _ = registry.get_or_insert_boxed("leak.bb".into());
+        _ = registry.get_or_insert_static_str("leak.ccc");
+        assert_eq!(registry.leaked_count(), 3);
+        assert_eq!(registry.leaked_bytes(), 6 + 7 + 3 * reference);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn new_static_str() {
+        let leaked: &'static str = std::string::String::from("new_static_str").leak();
+        let symbol = Symbol::new_static_str(leaked);
+        assert_eq!(symbol.as_str().as_ptr(), leaked.as_ptr());
+        assert_eq!(Symbol::new_static(&"new_static_str"), symbol);
+        assert_eq!(Symbol::new("new_static_str"), symbol);
+        assert_eq!(Symbol::new_static_str("new_static_str"), symbol);
+
+        let existing = Symbol::new_static(&"new_static_str.existing");
+        assert_eq!(Symbol::new_static_str("new_static_str.existing"), existing);
+    }
+
+    #[test]
+    fn try_new_borrowed() {
+        let borrowed = std::string::String::from("try_new_borrowed");
+        assert_eq!(Symbol::try_new_borrowed(&borrowed), Err(crate::NotInterned));
+        assert!(Symbol::get("try_new_borrowed").is_none());
+        let symbol = Symbol::new_static(&"try_new_borrowed");
+        assert_eq!(Symbol::try_new_borrowed(&borrowed), Ok(symbol));
+    }
+
+    #[test]
+    fn is_valid() {
+        assert!(Symbol::new_static(&"is_valid").is_valid());
+        let unregistered = unsafe {
+            // SAFETY: The symbol is only used to check that it is invalid.
+            Symbol::new_unchecked(&"is_valid.unregistered")
+        };
+        assert!(!unregistered.is_valid());
+    }
+
+    #[test]
+    fn contains() {
+        static STRING: &str = "contains";
+        assert!(!Registry::global().contains("contains"));
+        let _ = Symbol::new_static(&STRING);
+        assert!(Registry::global().contains("contains"));
+        assert!(Registry::global().read().contains("contains"));
+        assert!(!Registry::global().read().contains("contains.missing"));
+    }
+
+    #[test]
+    fn display_lisp() {
+        static FOO: &str = "foo";
+        static WITH_OPS: &str = "set-value!";
+        static SPACE: &str = "foo bar";
+        static QUOTE: &str = "a\"b";
+        static EMPTY: &str = "";
+        let lisp = |string| std::format!("{}", Symbol::new_static(string).display_lisp());
+        assert_eq!(lisp(&FOO), ":foo");
+        assert_eq!(lisp(&WITH_OPS), ":set-value!");
+        assert_eq!(lisp(&SPACE), r#":"foo bar""#);
+        assert_eq!(lisp(&QUOTE), r#":"a\"b""#);
+        assert_eq!(lisp(&EMPTY), r#":"""#);
+    }
+
+    #[test]
+    fn get_str() {
+        static STRING: &str = "get_str";
+        let symbol = Symbol::new_static(&STRING);
+        let input = std::format!("get{}", "_str");
+        let read = Registry::global().read();
+        let interned = read.get_str(&input).unwrap();
+        assert_eq!(interned, "get_str");
+        assert_eq!(interned.as_ptr(), symbol.as_str().as_ptr());
+        assert_eq!(read.get_str("get_str.missing"), None);
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "custom-lock")))]
+    #[test]
+    fn max_symbol_len() {
+        let registry = local_registry();
+        let long = registry.get_or_insert("max_symbol_len.long");
+        registry.set_max_symbol_len(16);
+        assert_eq!(
+            registry.try_get_or_insert("max_symbol_len.short"),
+            Err(InternError::SymbolTooLong { len: 20, max: 16 })
+        );
+        assert_eq!(registry.read().get("max_symbol_len.short"), None);
+        // Existing symbols still resolve.
+        assert_eq!(registry.try_get_or_insert("max_symbol_len.long"), Ok(long));
+        let short = registry.try_get_or_insert("short").unwrap();
+        assert_eq!(short, "short");
+        assert_eq!(
+            std::format!("{}", InternError::SymbolTooLong { len: 20, max: 16 }),
+            "symbol is too long (20 bytes, the maximum is 16)"
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn new_reporting() {
+        let (symbol, inserted) = Symbol::new_reporting("new_reporting");
+        assert!(inserted);
+        assert_eq!(symbol, "new_reporting");
+        assert_eq!(Symbol::new_reporting("new_reporting"), (symbol, false));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn try_new() {
+        assert_eq!(Symbol::try_new("try_new"), Ok(Symbol::new("try_new")));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn new_take() {
+        let mut buffer = std::string::String::from("new_take.miss");
+        let miss = Symbol::new_take(&mut buffer);
+        assert_eq!(miss, "new_take.miss");
+        assert!(buffer.is_empty());
+
+        buffer.push_str("new_take.miss");
+        let capacity = buffer.capacity();
+        let hit = Symbol::new_take(&mut buffer);
+        assert_eq!(hit, miss);
+        assert_eq!(buffer, "new_take.miss");
+        assert_eq!(buffer.capacity(), capacity);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn intern_all() {
+        let [a, b, a2] = Symbol::intern_all(["intern_all.a", "intern_all.b", "intern_all.a"]);
+        assert_eq!(a, "intern_all.a");
+        assert_eq!(b, "intern_all.b");
+        assert_eq!(a, a2);
+        assert_eq!(a, Symbol::new("intern_all.a"));
+        assert_eq!(Symbol::intern_all([]), [] as [Symbol; 0]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn require() {
+        use alloc::string::ToString;
+
+        _ = Symbol::new_static(&"require.present");
+        let registry = Registry::global();
+        assert_eq!(registry.require(&["require.present"]), Ok(()));
+        let err = registry
+            .require(&["require.present", "require.absent"])
+            .unwrap_err();
+        assert_eq!(err.names(), ["require.absent"]);
+        assert_eq!(
+            err.to_string(),
+            "missing required symbols: \"require.absent\""
+        );
+        assert!(Symbol::get("require.absent").is_none());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn scoped_pool() {
+        let pool = ScopedPool::new();
+        let a = pool.intern("scoped_pool.a");
+        assert_eq!(a, pool.intern("scoped_pool.a"));
+        assert_eq!(
+            a.as_str().as_ptr(),
+            pool.intern("scoped_pool.a").as_str().as_ptr()
+        );
+        assert_ne!(a, pool.intern("scoped_pool.b"));
+        assert_eq!(pool.len(), 2);
+
+        // Symbols are local to each pool.
+        let other = ScopedPool::new();
+        assert_eq!(other.get("scoped_pool.a"), None);
+        assert_eq!(other.intern("scoped_pool.a"), "scoped_pool.a");
+        drop(other);
+        assert!(Symbol::get("scoped_pool.a").is_none());
+
+        let mut pool = pool;
+        pool.retain(|string| string == "scoped_pool.b");
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.get("scoped_pool.a"), None);
+        assert_eq!(pool.get("scoped_pool.b").unwrap(), "scoped_pool.b");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn fuzz_registry() {
+        let mut registry = FuzzRegistry::new();
+        for iteration in 0..2 {
+            registry.clear();
+            assert!(registry.is_empty());
+
+            let a = registry.intern("a");
+            let b = registry.intern("b");
+            assert_eq!(a, registry.intern("a"));
+            assert_ne!(a, b);
+            assert_eq!(a, "a");
+            assert_eq!(registry.get("b"), Some(b));
+            assert_eq!(registry.get("c"), None);
+            if iteration == 0 {
+                _ = registry.intern("only in the first iteration");
+                assert_eq!(registry.len(), 3);
+            } else {
+                assert_eq!(registry.len(), 2);
+            }
+        }
+        // The global registry is not affected.
+        assert!(Symbol::get("only in the first iteration").is_none());
+    }
+
+    #[test]
+    fn iter_prefix() {
+        let a = Symbol::new_static(&"iter_prefix.a");
+        let b = Symbol::new_static(&"iter_prefix.b");
+        _ = Symbol::new_static(&"iter_prefix");
+
+        let read = Registry::global().read();
+        assert!(read.iter().any(|symbol| symbol == a));
+        let mut found = read
+            .iter_prefix("iter_prefix.")
+            .collect::<std::vec::Vec<_>>();
+        found.sort_by_key(Symbol::as_str);
+        assert_eq!(found, [a, b]);
+        assert_eq!(read.iter_prefix("iter_prefix.c").count(), 0);
+    }
+
+    #[test]
+    fn ffi_tagged() {
+        let symbol = Symbol::new_static(&"ffi_tagged");
+        let tagged = symbol.to_ffi_tagged();
+        assert_eq!(tagged, (Registry::abi_version(), symbol.to_ffi()));
+        assert_eq!(Symbol::try_from_ffi_tagged(tagged), Some(symbol));
+        assert_eq!(
+            Symbol::try_from_ffi_tagged((Registry::abi_version() + 1, symbol.to_ffi())),
+            None
+        );
+        assert_eq!(
+            Symbol::try_from_ffi_tagged((Registry::abi_version(), 0xdead_beef)),
+            None
+        );
+    }
+
+    #[test]
+    fn try_from_ffi_slice() {
+        let a = Symbol::new_static(&"ffi_slice.a");
+        let b = Symbol::new_static(&"ffi_slice.b");
+        let handles = [a.to_ffi(), 0, b.to_ffi()];
+        let mut out = [None; 3];
+        let resolved = Registry::global().try_from_ffi_slice(&handles, &mut out);
+        assert_eq!(resolved, 2);
+        assert_eq!(out, [Some(a), None, Some(b)]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn ffi_debug() {
+        use alloc::string::ToString;
+
+        let a = Symbol::new_static(&"ffi \"debug\"");
+        let formatted = a.ffi_debug().to_string();
+        assert!(formatted.contains(&alloc::format!("{:x}", a.to_ffi())));
+        assert_eq!(
+            formatted,
+            alloc::format!("sym#{:x}(\"ffi \\\"debug\\\"\")", a.to_ffi())
+        );
+    }
+
+    #[cfg(feature = "capi")]
+    #[test]
+    fn capi_header() {
+        use std::{string::String, vec::Vec};
+
+        // `stringleton.h` is written by hand, so check that it declares
+        // exactly the functions in `capi.rs`, with matching signatures.
+        fn c_decl(rust_type: &str, name: &str) -> String {
+            let c_type = match rust_type.trim() {
+                "SymbolFfi" => "SymbolFfi",
+                "usize" => "size_t",
+                "*const u8" => "const uint8_t *",
+                "*mut usize" => "size_t *",
+                other => panic!("no C type for `{other}`, extend this test"),
+            };
+            if c_type.ends_with('*') {
+                std::format!("{c_type}{name}")
+            } else {
+                std::format!("{c_type} {name}")
+            }
+        }
+        let normalize = |code: &str| code.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let header = normalize(include_str!("stringleton.h"));
+        let source = include_str!("capi.rs");
+        let mut functions = 0;
+        for item in source.split("extern \"C\" fn ").skip(1) {
+            let (name, rest) = item.split_once('(').unwrap();
+            let (params, rest) = rest.split_once(')').unwrap();
+            let params: Vec<String> = params
+                .split(',')
+                .filter(|param| !param.trim().is_empty())
+                .map(|param| {
+                    let (name, rust_type) = param.split_once(':').unwrap();
+                    c_decl(rust_type, name.trim())
+                })
+                .collect();
+            let params = if params.is_empty() {
+                String::from("void")
+            } else {
+                params.join(", ")
+            };
+            let signature = std::format!("{name}({params});");
+            let declaration = match rest.split_once('{').unwrap().0.trim().strip_prefix("->") {
+                Some(return_type) => c_decl(return_type, &signature),
+                None => std::format!("void {signature}"),
+            };
+            assert!(
+                header.contains(&declaration),
+                "stringleton.h does not declare `{declaration}`"
+            );
+            functions += 1;
+        }
+        assert_eq!(header.matches("stringleton_").count(), functions);
+    }
+
+    #[cfg(feature = "capi")]
+    #[test]
+    fn capi() {
+        use capi::{stringleton_symbol_as_str, stringleton_symbol_from_str};
+
+        let string = "capi";
+        let handle = unsafe { stringleton_symbol_from_str(string.as_ptr(), string.len()) };
+        assert_eq!(handle, SymbolFfi::from(Symbol::new("capi")));
+
+        let mut len = 0;
+        let ptr = unsafe { stringleton_symbol_as_str(handle, &raw mut len) };
+        assert_eq!(unsafe { core::slice::from_raw_parts(ptr, len) }, b"capi");
+
+        assert!(unsafe { stringleton_symbol_as_str(SymbolFfi(0), &raw mut len) }.is_null());
+        let invalid = [0xff, 0xfe];
+        assert_eq!(
+            unsafe { stringleton_symbol_from_str(invalid.as_ptr(), invalid.len()) },
+            SymbolFfi(0)
+        );
+        let empty = unsafe { stringleton_symbol_from_str(core::ptr::null(), 0) };
+        assert_eq!(empty, SymbolFfi::from(Symbol::new("")));
+    }
+
+    #[cfg(feature = "clap")]
+    #[test]
+    fn clap_value_parser() {
+        use clap::{Arg, Command, error::ErrorKind, value_parser};
+
+        let command = Command::new("test")
+            .arg(
+                Arg::new("name")
+                    .long("name")
+                    .value_parser(value_parser!(Symbol)),
+            )
+            .arg(
+                Arg::new("mode")
+                    .long("mode")
+                    .ignore_case(true)
+                    .value_parser(SymbolValueParser::known([
+                        Symbol::new_static(&"clap.fast"),
+                        Symbol::new_static(&"clap.safe"),
+                    ])),
+            );
+        let matches = command
+            .clone()
+            .try_get_matches_from(["test", "--name", "clap.name", "--mode", "CLAP.SAFE"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<Symbol>("name"),
+            Some(&Symbol::new("clap.name"))
+        );
+        assert_eq!(
+            matches.get_one::<Symbol>("mode"),
+            Some(&Symbol::new("clap.safe"))
+        );
+
+        let mode = command.get_arguments().find(|arg| arg.get_id() == "mode");
+        let possible = mode.unwrap().get_possible_values();
+        assert!(
+            possible
+                .iter()
+                .map(clap::builder::PossibleValue::get_name)
+                .eq(["clap.fast", "clap.safe"])
+        );
+
+        let err = command
+            .try_get_matches_from(["test", "--mode", "clap.unknown"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidValue);
+        assert!(Symbol::get("clap.unknown").is_none());
+    }
+
+    #[test]
+    fn register_sites_safe() {
+        static A: &str = "register_sites_safe.a";
+        static TABLE: [Site; 3] = [
+            Site::new(&A),
+            Site::new(&"register_sites_safe.b"),
+            Site::new(&"register_sites_safe.a"),
+        ];
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let threads: [_; 4] = core::array::from_fn(|_| {
+                std::thread::spawn(|| Registry::global().register_sites_safe(&TABLE))
+            });
+            for thread in threads {
+                thread.join().unwrap();
+            }
+        }
+        Registry::global().register_sites_safe(&TABLE);
+        // Registering again is harmless.
+        Registry::global().register_sites_safe(&TABLE);
+
+        let [a, b, a2] = TABLE.each_ref().map(|site| unsafe {
+            // SAFETY: The table has been registered.
+            site.get_after_ctor()
+        });
+        assert_eq!(a, Symbol::new_static(&"register_sites_safe.a"));
+        assert_eq!(b, Symbol::new_static(&"register_sites_safe.b"));
+        assert_eq!(a, a2);
+        assert_ne!(a, b);
+    }
+
+    #[cfg(all(feature = "debug-assertions", not(any(miri, target_arch = "wasm32"))))]
+    #[test]
+    fn uninitialized_site_handler() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static HANDLED: AtomicUsize = AtomicUsize::new(0);
+        static SITE: Site = Site::new(&"uninitialized_site");
+
+        Registry::global().set_uninitialized_site_handler(|site| {
+            assert!(core::ptr::eq(site, &raw const SITE));
+            HANDLED.fetch_add(1, Ordering::Relaxed);
+        });
+        // SAFETY: The site is not part of a symbol table, which is what the
+        // handler is for.
+        let symbol = unsafe { SITE.get_after_ctor() };
+        assert_eq!(symbol, Symbol::new_static(&"uninitialized_site"));
+        assert_eq!(unsafe { SITE.get_after_ctor() }, symbol);
+        assert_eq!(HANDLED.load(Ordering::Relaxed), 1);
+    }
+
+    #[cfg(feature = "phf")]
+    #[test]
+    fn phf_get() {
+        static MAP: phf::Map<&'static str, u32> = phf::phf_map! {
+            "phf_get.a" => 1,
+            "phf_get.b" => 2,
+        };
+        assert_eq!(Symbol::new_static(&"phf_get.b").phf_get(&MAP), Some(&2));
+        assert_eq!(Symbol::new_static(&"phf_get.c").phf_get(&MAP), None);
+    }
+
+    #[cfg(feature = "compact_str")]
+    #[test]
+    fn compact_str() {
+        use compact_str::CompactString;
+
+        let symbol = Symbol::from(CompactString::new("compact_str"));
+        assert_eq!(symbol, Symbol::new_static(&"compact_str"));
+        let long = Symbol::new("compact_str, but longer than the inline capacity");
+        let string = CompactString::from(long);
+        assert_eq!(string, long.as_str());
+        assert_eq!(string.as_ptr(), long.as_str().as_ptr());
+    }
+
+    #[cfg(feature = "smol_str")]
+    #[test]
+    fn smol_str() {
+        use smol_str::SmolStr;
+
+        let symbol = Symbol::from(SmolStr::new("smol_str"));
+        assert_eq!(symbol, Symbol::new_static(&"smol_str"));
+        let long = Symbol::new("smol_str, but longer than the inline capacity");
+        let string = SmolStr::from(long);
+        assert_eq!(string, long.as_str());
+        assert!(!string.is_heap_allocated());
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn ffi_bytemuck() {
+        let handles = [
+            SymbolFfi::from(Symbol::new_static(&"a")),
+            SymbolFfi::from(Symbol::new_static(&"b")),
+        ];
+        let bytes: &[u8] = bytemuck::cast_slice(&handles);
+        assert_eq!(bytes.len(), 16);
+        let roundtrip: &[SymbolFfi] = bytemuck::cast_slice(bytes);
+        assert_eq!(Symbol::try_from(roundtrip[0]), Ok(Symbol::new_static(&"a")));
+        assert_eq!(Symbol::try_from(roundtrip[1]), Ok(Symbol::new_static(&"b")));
+        assert!(Symbol::try_from(<SymbolFfi as bytemuck::Zeroable>::zeroed()).is_err());
+    }
+
+    #[test]
+    #[allow(clippy::manual_hash_one)] // Comparing against the manual path is the point.
+    fn hash_one() {
+        use core::hash::{BuildHasher, Hash, Hasher};
+
+        let state = hashbrown::DefaultHashBuilder::default();
+        let a = Symbol::new_static(&"hash_one");
+        let mut hasher = state.build_hasher();
+        a.hash(&mut hasher);
+        assert_eq!(a.hash_one(&state), hasher.finish());
+        assert_eq!(a.hash_one(&state), state.hash_one(a));
+    }
+
+    #[cfg(feature = "stable-hash")]
+    #[test]
+    fn stable_hash() {
+        use core::hash::BuildHasher;
+        let state = std::hash::BuildHasherDefault::<std::hash::DefaultHasher>::default();
+        assert_eq!(
+            state.hash_one(Symbol::new_static(&"stable")),
+            state.hash_one("stable")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn iter_ordered() {
+        let names = ["ordered.c", "ordered.a", "ordered.b"];
+        let symbols = names.map(Symbol::new);
+        _ = Symbol::new("ordered.a");
+        let read = Registry::global().read();
+        let ordered = read
+            .iter_ordered()
+            .filter(|symbol| symbol.as_str().starts_with("ordered."))
+            .collect::<std::vec::Vec<_>>();
+        assert_eq!(ordered, symbols);
+        assert_eq!(read.iter_ordered().count(), read.len());
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", not(feature = "custom-lock")))]
+    fn ids() {
+        // Other tests may create symbols concurrently, so consecutive IDs are
+        // only checked on a separate registry.
+        let registry = local_registry();
+        let local_a = registry.get_or_insert("ids.a");
+        let local_b = registry.get_or_insert("ids.b");
+        let read = registry.read();
+        assert_eq!(read.id_of(local_a), Some(0));
+        assert_eq!(read.id_of(local_b), Some(1));
+        assert_eq!(read.get_by_id(1), Some(local_b));
+        drop(read);
+
+        let a = Symbol::new("ids.a");
+        let b = Symbol::new("ids.b");
+        assert!(a.id() < b.id());
+        assert_eq!(Symbol::from_id(a.id()), Some(a));
+        assert_eq!(Symbol::from_id(u32::MAX), None);
+
+        let mut sorted = [b, a, b];
+        sorted.sort_by(Symbol::cmp_by_id);
+        assert_eq!(sorted, [a, b, b]);
+        let z = Symbol::new("ids.0");
+        assert_eq!(z.cmp_by_id(&a), core::cmp::Ordering::Greater);
+        assert_eq!(a.cmp_by_id(&a), core::cmp::Ordering::Equal);
+
+        let table = Registry::global().read().export_table();
+        assert!(table.contains(&(a.id(), "ids.a")));
+        assert!(table.iter().zip(0..).all(|((id, _), i)| *id == i));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn symbol_bit_set() {
+        let a = Symbol::new("bit_set.a");
+        let b = Symbol::new("bit_set.b");
+        let c = Symbol::new("bit_set.c");
+        let mut set = SymbolBitSet::new();
+        assert!(set.is_empty());
+        assert!(set.insert(c));
+        assert!(set.insert(a));
+        assert!(!set.insert(a));
+        assert!(set.contains(a));
+        assert!(!set.contains(b));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter().collect::<std::vec::Vec<_>>(), [a, c]);
+        assert!(set.ids().eq([a.id(), c.id()]));
+        let [a_id, b_id] = Symbol::ids_of([a, b]);
+        assert_eq!([a_id, b_id], [a.id(), b.id()]);
+        assert!(set.contains_id(a_id));
+        assert!(!set.contains_id(b_id));
+
+        assert!(set.insert_id(1000));
+        assert!(set.contains_id(1000));
+        assert!(set.remove_id(1000));
+        assert!(!set.remove_id(1000));
+        assert!(!set.remove_id(u32::MAX));
+        assert_eq!(set, [c, a].into_iter().collect());
+        assert!(set.remove(c));
+        assert!(!set.remove(b));
+        assert_eq!(std::format!("{set:?}"), "{\"bit_set.a\"}");
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set, SymbolBitSet::default());
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", not(feature = "custom-lock")))]
+    fn import_table() {
+        use std::panic::AssertUnwindSafe;
+
+        // Uses a separate registry, because this reassigns IDs.
+        let registry = local_registry();
+        _ = registry.get_or_insert("import.existing");
+        registry.import_table(&[(1, "import.b"), (0, "import.existing"), (2, "import.a")]);
+        let read = registry.read();
+        let ids: std::vec::Vec<_> = read.export_table();
+        assert_eq!(
+            ids,
+            [(0, "import.existing"), (1, "import.b"), (2, "import.a")]
+        );
+        let b = read.get("import.b").unwrap();
+        assert_eq!(read.id_of(b), Some(1));
+        assert_eq!(read.get_by_id(2), read.get("import.a"));
+        drop(read);
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            registry.import_table(&[(0, "import.a"), (1, "import.a")]);
+        }));
+        assert!(result.is_err());
+        assert_eq!(registry.read().export_table(), ids);
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            registry.import_table(&[(1, "import.a")]);
+        }));
+        assert!(result.is_err());
+        assert_eq!(registry.read().export_table(), ids);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn import_table_after_id() {
+        let symbol = Symbol::new("import_table_after_id");
+        let id = symbol.id();
+        let result = std::panic::catch_unwind(|| {
+            Registry::global().import_table(&[(0, "import_table_after_id")]);
+        });
+        let message = *result.unwrap_err().downcast::<&str>().unwrap();
+        assert_eq!(
+            message,
+            "symbol table imported after symbol IDs were observed"
+        );
+        assert_eq!(symbol.id(), id);
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", not(feature = "custom-lock")))]
+    fn sorted_strings() {
+        let registry = local_registry();
+        assert!(registry.read().sorted_strings().is_empty());
+        for string in ["sorted.c", "sorted.a", "sorted.B", "sorted.b", "sorted.a"] {
+            _ = registry.get_or_insert(string);
+        }
+        assert_eq!(
+            registry.read().sorted_strings(),
+            ["sorted.B", "sorted.a", "sorted.b", "sorted.c"]
+        );
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn find_near_duplicates() {
+        let a = Symbol::new("nearDup renderOrder");
+        let b = Symbol::new("near_dup render_order");
+        let c = Symbol::new("near-dup-render-order");
+        _ = Symbol::new("near_dup render_orders");
+        let near_dups = || {
+            Registry::global()
+                .find_near_duplicates()
+                .into_iter()
+                .filter(|(a, _)| a.as_str().to_ascii_lowercase().starts_with("near"))
+                .collect::<std::vec::Vec<_>>()
+        };
+        let pairs = near_dups();
+        assert_eq!(pairs, [(c, a), (c, b), (a, b)]);
+        assert_eq!(pairs, near_dups());
+    }
+
+    #[cfg(all(feature = "diagnostics", not(feature = "custom-lock")))]
+    #[test]
+    fn length_histogram() {
+        let registry = local_registry();
+        let long = "x".repeat(100);
+        for string in ["", "a", "b", "abc", "x".repeat(63).as_str(), &long] {
+            _ = registry.get_or_insert(string);
+        }
+        let histogram = registry.read().length_histogram();
+        assert_eq!(histogram[0], 1);
+        assert_eq!(histogram[1], 2);
+        assert_eq!(histogram[2], 0);
+        assert_eq!(histogram[3], 1);
+        assert_eq!(histogram[63], 2);
+        assert_eq!(histogram.iter().sum::<usize>(), registry.read().len());
+    }
+
+    #[cfg(feature = "unicode-norm")]
+    #[test]
+    fn new_normalized() {
+        let composed = Symbol::new_normalized("normalized caf\u{e9}");
+        let decomposed = Symbol::new_normalized("normalized cafe\u{301}");
+        assert_eq!(composed, decomposed);
+        assert_eq!(decomposed.as_str(), "normalized caf\u{e9}");
+        assert_ne!(Symbol::new("normalized cafe\u{301}"), composed);
+
+        // Hangul syllables compose from their jamo.
+        assert_eq!(
+            Symbol::new_normalized("normalized \u{1100}\u{1161}"),
+            Symbol::new_normalized("normalized \u{ac00}")
+        );
+        // Not case folding.
+        assert_ne!(
+            Symbol::new_normalized("normalized Caf\u{e9}"),
+            Symbol::new_normalized("normalized cafe\u{301}")
+        );
+        assert_eq!(
+            Symbol::new_normalized("normalized ascii"),
+            Symbol::new_static(&"normalized ascii")
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn symbol_builder() {
+        let mut builder = SymbolBuilder::new();
+        builder.push("builder").sep(':').push("ns");
+        for name in ["a", "b"] {
+            builder.push_sym(Symbol::new_static(&"sub")).push(name);
+        }
+        assert_eq!(builder.as_str(), "builder:ns:sub:a:sub:b");
+        let symbol = builder.finish();
+        assert_eq!(symbol, Symbol::new("builder:ns:sub:a:sub:b"));
+        assert_eq!(builder.as_str(), "");
+
+        // The separator is kept, but not inserted before the first segment.
+        builder.push("builder").push("");
+        assert_eq!(builder.finish(), Symbol::new("builder:"));
+        builder.push("").push("builder");
+        assert_eq!(builder.finish(), Symbol::new(":builder"));
+
+        let mut unseparated = SymbolBuilder::with_capacity(16);
+        unseparated.push("builder").push(".x");
+        assert_eq!(unseparated.finish(), Symbol::new("builder.x"));
+        assert_eq!(SymbolBuilder::default().finish(), Symbol::new(""));
+    }
+
+    #[cfg(all(feature = "alloc", not(feature = "custom-lock")))]
+    #[test]
+    fn str_data_ptr() {
+        static STRING: &str = "str_data_ptr";
+        let registry = local_registry();
+        let global = Symbol::new_static_str(STRING);
+        let local = registry.get_or_insert_static_str(STRING);
+        assert_ne!(global.as_ptr(), local.as_ptr());
+        assert_eq!(global.str_data_ptr(), local.str_data_ptr());
+        assert_eq!(global.str_data_ptr(), STRING.as_ptr());
+
+        let copied = registry.get_or_insert("str_data_ptr.copied");
+        assert_ne!(copied.str_data_ptr(), "str_data_ptr.copied".as_ptr());
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn assert_same() {
+        let a = Symbol::new_static(&"assert_same");
+        Symbol::assert_same(a, Symbol::new_static(&"assert_same"));
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    #[should_panic = "symbols are not the same\n  left: sym#"]
+    fn assert_same_different() {
+        Symbol::assert_same(
+            Symbol::new_static(&"assert_same.a"),
+            Symbol::new_static(&"assert_same.b"),
+        );
+    }
+
+    #[cfg(all(feature = "test-util", feature = "alloc", not(feature = "custom-lock")))]
+    #[test]
+    #[should_panic = "symbols are not the same (equal strings from different registries)"]
+    fn assert_same_different_registry() {
+        let registry = local_registry();
+        Symbol::assert_same(
+            Symbol::new_static(&"assert_same.registry"),
+            registry.get_or_insert("assert_same.registry"),
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    #[test]
+    fn deserialize_batch() {
+        use serde::de::DeserializeSeed;
+
+        let mut de = serde_json::Deserializer::from_str(r#"["seq.a", "seq.b", "seq.a"]"#);
+        let symbols = Registry::global()
+            .batch()
+            .seq()
+            .deserialize(&mut de)
+            .unwrap();
+        assert_eq!(symbols, ["seq.a", "seq.b", "seq.a"]);
+        assert_eq!(symbols[0], symbols[2]);
+
+        let mut de = serde_json::Deserializer::from_str(r#""seq.b""#);
+        let b = Registry::global().batch().deserialize(&mut de).unwrap();
+        assert_eq!(b, symbols[1]);
+
+        let mut de = serde_json::Deserializer::from_str("[1]");
+        assert!(
+            Registry::global()
+                .batch()
+                .seq()
+                .deserialize(&mut de)
+                .is_err()
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    #[test]
+    fn rmp_serde_roundtrip() {
+        let a = Symbol::new("rmp");
+        let bytes = rmp_serde::to_vec(&a).unwrap();
+        // Symbols are encoded as plain MessagePack strings.
+        assert_eq!(bytes, rmp_serde::to_vec("rmp").unwrap());
+        let a2: Symbol = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(a, a2);
+    }
+
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    #[test]
+    fn serde_map_keys() {
+        use std::collections::BTreeMap;
+
+        let map: BTreeMap<Symbol, i32> = [
+            (Symbol::new("map_keys.a"), 1),
+            (Symbol::new("map_keys \"quoted\""), 2),
+            (Symbol::new(""), 3),
+        ]
+        .into_iter()
+        .collect();
+
+        let json = serde_json::to_string(&map).unwrap();
+        assert!(json.contains(r#""map_keys.a":1"#));
+        assert!(json.contains(r#""map_keys \"quoted\"":2"#));
+        assert!(json.contains(r#""":3"#));
+        let map2: BTreeMap<Symbol, i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(map, map2);
+
+        // Escaped keys cannot be borrowed from the input.
+        let map3: BTreeMap<Symbol, i32> =
+            serde_json::from_str(r#"{"map_keys.\u0062": 4}"#).unwrap();
+        assert_eq!(map3[&Symbol::new("map_keys.b")], 4);
+        let value: Symbol = serde_json::from_value(serde_json::json!("map_keys.owned")).unwrap();
+        assert_eq!(value, Symbol::new("map_keys.owned"));
+
+        let bytes = rmp_serde::to_vec(&map).unwrap();
+        let map4: BTreeMap<Symbol, i32> = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(map, map4);
+
+        assert!(serde_json::from_str::<Symbol>("1").is_err());
+    }
 }
@@ -0,0 +1,76 @@
+use crate::Symbol;
+
+#[doc(hidden)]
+pub use phf;
+
+/// Compile-time perfect hash map from symbol strings to values.
+///
+/// Created with the `perfect_symbol_set!` macro in the `stringleton` crate.
+/// The hash map is built by [`phf`] at compile time, so lookups never take the
+/// registry lock and never allocate. Lookups hash the string contents of the
+/// symbol, so they are slower than a pointer comparison, but constant-time
+/// regardless of the number of entries.
+///
+/// This is only useful for static, closed sets of strings, such as the
+/// keywords or builtin functions of a language. For sets that change at
+/// runtime, use a `HashMap<Symbol, V>` instead.
+pub struct PerfectSymbolSet<V: 'static> {
+    map: phf::Map<&'static str, V>,
+}
+
+impl<V: 'static> PerfectSymbolSet<V> {
+    #[doc(hidden)]
+    #[inline]
+    #[must_use]
+    pub const fn from_phf(map: phf::Map<&'static str, V>) -> Self {
+        Self { map }
+    }
+
+    /// Get the value for `symbol`, if it is in the set.
+    #[inline]
+    #[must_use]
+    pub fn lookup(&self, symbol: Symbol) -> Option<&V> {
+        self.map.get(symbol.as_str())
+    }
+
+    /// Get the value for the string `s`, if it is in the set.
+    ///
+    /// This does not create a symbol for `s`.
+    #[inline]
+    #[must_use]
+    pub fn lookup_str(&self, s: &str) -> Option<&V> {
+        self.map.get(s)
+    }
+
+    /// Check if `symbol` is in the set.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, symbol: Symbol) -> bool {
+        self.map.contains_key(symbol.as_str())
+    }
+
+    /// The number of entries in the set.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Check if the set is empty.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterate over the strings and values in the set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &V)> {
+        self.map.entries().map(|(k, v)| (*k, v))
+    }
+}
+
+impl<V: core::fmt::Debug + 'static> core::fmt::Debug for PerfectSymbolSet<V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
@@ -1,24 +1,35 @@
 use core::{borrow::Borrow, hash::Hash};
 
 use crate::{Site, Symbol};
+use alloc::vec::Vec;
 use hashbrown::{HashMap, hash_map};
 
 #[cfg(feature = "alloc")]
-use alloc::{borrow::ToOwned, boxed::Box};
+use crate::{GlobalSymbolAllocator, LeakingSymbolAllocator};
+#[cfg(feature = "alloc")]
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec};
 
-#[cfg(not(any(feature = "std", feature = "critical-section")))]
-compile_error!("Either the `std` or `critical-section` feature must be enabled");
-#[cfg(not(any(feature = "std", feature = "spin")))]
-compile_error!("Either the `std` or `spin` feature must be enabled");
+#[cfg(not(any(feature = "std", feature = "critical-section", feature = "custom-lock")))]
+compile_error!("Either the `std`, `critical-section`, or `custom-lock` feature must be enabled");
+#[cfg(not(any(feature = "std", feature = "spin", feature = "custom-lock")))]
+compile_error!("Either the `std`, `spin`, or `custom-lock` feature must be enabled");
+// Features are unified across the dependency graph, so without this, one crate
+// enabling `custom-lock` would silently replace the lock of every other user.
+#[cfg(all(feature = "custom-lock", any(feature = "std", feature = "spin")))]
+compile_error!(
+    "The `custom-lock` feature cannot be combined with the `std` or `spin` features (disable the default features to use it)"
+);
 
-#[cfg(feature = "spin")]
+#[cfg(feature = "custom-lock")]
+use crate::custom_lock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+#[cfg(all(feature = "spin", not(feature = "custom-lock")))]
 use spin::{RwLock, RwLockReadGuard, RwLockWriteGuard};
-#[cfg(not(feature = "spin"))]
+#[cfg(not(any(feature = "spin", feature = "custom-lock")))]
 use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 
-#[cfg(feature = "critical-section")]
+#[cfg(all(feature = "critical-section", not(feature = "custom-lock")))]
 use once_cell::sync::OnceCell as OnceLock;
-#[cfg(not(feature = "critical-section"))]
+#[cfg(not(any(feature = "critical-section", feature = "custom-lock")))]
 use std::sync::OnceLock;
 
 /// Helper to control the behavior of symbol strings in the registry's hash map.
@@ -42,58 +53,184 @@ impl Hash for SymbolStr {
     }
 }
 
-#[cfg(feature = "alloc")]
-impl From<&str> for SymbolStr {
-    #[inline]
-    fn from(value: &str) -> Self {
-        let value = &*Box::leak(Box::new(&*value.to_owned().leak()));
-        Self(value)
-    }
-}
-
 /// The global symbol registry.
 ///
 /// This is available for advanced use cases, such as bulk-insertion of many
 /// symbols.
 pub struct Registry {
-    #[cfg(not(feature = "spin"))]
-    store: std::sync::RwLock<Store>,
-    #[cfg(feature = "spin")]
-    store: spin::RwLock<Store>,
+    // Note: Either `std`, `spin`, or `custom-lock`.
+    store: RwLock<Store>,
+    #[cfg(feature = "profiling")]
+    counters: AccessCounters,
+}
+
+/// Counters behind [`Registry::access_stats()`].
+///
+/// These are updated with relaxed atomics outside of the lock, so they are
+/// only approximately consistent with each other.
+#[cfg(feature = "profiling")]
+struct AccessCounters {
+    read_hits: core::sync::atomic::AtomicUsize,
+    write_inserts: core::sync::atomic::AtomicUsize,
+}
+
+#[cfg(feature = "profiling")]
+impl AccessCounters {
+    const fn new() -> Self {
+        Self {
+            read_hits: core::sync::atomic::AtomicUsize::new(0),
+            write_inserts: core::sync::atomic::AtomicUsize::new(0),
+        }
+    }
+
+    #[inline]
+    fn read_hit(&self) {
+        self.read_hits
+            .fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn write_insert(&self, inserted: bool) {
+        if inserted {
+            self.write_inserts
+                .fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        }
+    }
 }
 
 #[derive(Default)]
 pub(crate) struct Store {
+    /// Note: This uses hashbrown's default hasher, which is `foldhash`, a fast
+    /// non-cryptographic hash that is well suited to short identifiers. It is
+    /// randomly seeded, which provides some resistance against hash flooding.
     by_string: HashMap<SymbolStr, ()>,
-    by_pointer: HashMap<usize, SymbolStr>,
+    /// Maps the address of each symbol to its ID.
+    by_pointer: HashMap<usize, u32>,
+    /// All symbols, indexed by ID. IDs are assigned in registration order,
+    /// unless reassigned by `Registry::import_table()`.
+    order: Vec<SymbolStr>,
+    /// Set when an ID has been handed out, after which
+    /// `Registry::import_table()` may no longer reassign IDs. This is atomic
+    /// because IDs are looked up under the read lock.
+    #[cfg(feature = "alloc")]
+    ids_observed: core::sync::atomic::AtomicBool,
+    /// Number of `sym!()` call sites registered by static initializers.
+    site_count: usize,
+    /// Addresses of all registered `sym!()` call sites, with the address of
+    /// the symbol they resolved to.
+    #[cfg(feature = "introspection")]
+    sites: Vec<(usize, usize)>,
+    /// Maps the address of each symbol to the source location of the first
+    /// registered call site that resolved to it.
+    #[cfg(feature = "track-caller-sites")]
+    locations: HashMap<usize, (&'static str, u32)>,
+    #[cfg(feature = "alloc")]
+    allocator: Option<&'static dyn GlobalSymbolAllocator>,
+    /// Maximum length of new symbols created by `Symbol::try_new()`.
+    #[cfg(feature = "alloc")]
+    max_len: Option<usize>,
+    /// Number of symbols for which the registry allocated memory.
+    #[cfg(feature = "alloc")]
+    leaked_count: usize,
+    /// Number of bytes allocated for those symbols.
+    #[cfg(feature = "alloc")]
+    leaked_bytes: usize,
+    /// Called when a call site is reached before it was initialized.
+    #[cfg(feature = "debug-assertions")]
+    uninitialized_site_handler: Option<fn(&Site)>,
 }
 
 /// Symbol registry read lock guard
 pub struct RegistryReadGuard {
-    // Note: Either `std` or `spin`.
+    // Note: Either `std`, `spin`, or `custom-lock`.
     guard: RwLockReadGuard<'static, Store>,
 }
 
 /// Symbol registry write lock guard
 pub struct RegistryWriteGuard {
-    // Note: Either `std` or `spin`.
+    // Note: Either `std`, `spin`, or `custom-lock`.
     guard: RwLockWriteGuard<'static, Store>,
 }
 
+/// Interner for a batch of symbols, holding the registry's write lock.
+///
+/// This is created by [`Registry::batch()`].
+///
+/// **The global write lock is held for as long as this object is alive**, and
+/// released when it is dropped. While it is held, other threads cannot create
+/// or look up symbols through the registry, so keep the batch short. Accessing
+/// the string representation of existing symbols is not affected.
+#[must_use = "the write lock is released when the batch interner is dropped"]
+pub struct BatchInterner {
+    guard: RegistryWriteGuard,
+}
+
 impl Registry {
     #[inline]
-    fn new() -> Self {
+    #[cfg(not(feature = "custom-lock"))]
+    pub(crate) fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Create a registry with room for at least `capacity` symbols.
+    #[cfg(not(feature = "custom-lock"))]
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        let mut store = Store::default();
+        store.reserve(capacity);
         Self {
-            store: RwLock::default(),
+            store: RwLock::new(store),
+            #[cfg(feature = "profiling")]
+            counters: AccessCounters::new(),
         }
     }
 
     /// Get the global registry.
+    #[cfg(not(feature = "custom-lock"))]
+    #[must_use]
     pub fn global() -> &'static Registry {
         static REGISTRY: OnceLock<Registry> = OnceLock::new();
         REGISTRY.get_or_init(Registry::new)
     }
 
+    /// Get the global registry.
+    #[cfg(feature = "custom-lock")]
+    #[must_use]
+    pub fn global() -> &'static Registry {
+        // The custom lock initializes the store lazily, so no `OnceLock` is
+        // needed.
+        static REGISTRY: Registry = Registry {
+            store: RwLock::new(),
+            #[cfg(feature = "profiling")]
+            counters: AccessCounters::new(),
+        };
+        &REGISTRY
+    }
+
+    /// The version of the in-memory representation of symbols.
+    ///
+    /// FFI handles (see [`Symbol::to_ffi()`]) are addresses inside the
+    /// registry, so they can only be exchanged between binaries that agree on
+    /// how symbols are represented. This number changes whenever that
+    /// representation changes, so a dynamically loaded plugin can be rejected
+    /// up front if it was built against an incompatible version of this crate.
+    ///
+    /// A typical negotiation is for the plugin to export a function that
+    /// returns the `abi_version()` it was built with, which the host compares
+    /// to its own before exchanging any symbols. For a per-handle check, use
+    /// [`Symbol::to_ffi_tagged()`] and [`Symbol::try_from_ffi_tagged()`].
+    ///
+    /// Note that equal versions do not imply that the plugin shares the
+    /// registry of the host (see `stringleton-dylib`), which is what
+    /// [`Symbol::try_from_ffi()`] checks.
+    #[inline]
+    #[must_use]
+    pub const fn abi_version() -> u32 {
+        // Bump this whenever the representation of `Symbol` or of FFI handles
+        // changes.
+        const ABI_VERSION: u32 = 1;
+        ABI_VERSION
+    }
+
     /// Acquire a global read lock of the registry's data.
     ///
     /// New symbols cannot be created while the read lock is held, but acquiring
@@ -102,33 +239,90 @@ impl Registry {
     #[inline]
     pub fn read(&'static self) -> RegistryReadGuard {
         RegistryReadGuard {
-            #[cfg(not(feature = "spin"))]
+            #[cfg(not(any(feature = "spin", feature = "custom-lock")))]
             guard: self
                 .store
                 .read()
                 .unwrap_or_else(std::sync::PoisonError::into_inner),
-            #[cfg(feature = "spin")]
+            #[cfg(any(feature = "spin", feature = "custom-lock"))]
             guard: self.store.read(),
         }
     }
 
     /// Acquire a global write lock of the registry's data.
     ///
+    /// With the `std` lock, poisoning is ignored. This is sound, because the
+    /// registry never leaves its data in an inconsistent state when a panic
+    /// occurs while inserting a symbol (for example in a custom
+    /// [`GlobalSymbolAllocator`]).
+    ///
     /// Note that acquiring this lock does not prevent other threads from
     /// reading the string representation of a [`Symbol`].
     #[inline]
     pub fn write(&'static self) -> RegistryWriteGuard {
         RegistryWriteGuard {
-            #[cfg(not(feature = "spin"))]
+            #[cfg(not(any(feature = "spin", feature = "custom-lock")))]
             guard: self
                 .store
                 .write()
                 .unwrap_or_else(std::sync::PoisonError::into_inner),
-            #[cfg(feature = "spin")]
+            #[cfg(any(feature = "spin", feature = "custom-lock"))]
             guard: self.store.write(),
         }
     }
 
+    /// Reserve room for at least `capacity` symbols in total in the global
+    /// registry, to avoid growing its hash tables later.
+    ///
+    /// This is intended for services that intern a predictable number of
+    /// symbols, and should be called at the start of `main()`, before creating
+    /// any symbols at runtime. Symbols from `sym!()` call sites have already
+    /// been registered at that point, and count towards `capacity`. If the
+    /// registry already has room for `capacity` symbols, this does nothing.
+    pub fn init_global_with_capacity(capacity: usize) {
+        let mut write = Self::global().write();
+        let additional = capacity.saturating_sub(write.len());
+        write.guard.reserve(additional);
+    }
+
+    /// Start interning a batch of symbols under a single write lock.
+    ///
+    /// This is a convenience wrapper around [`write()`](Self::write) for code
+    /// that interns a burst of symbols at once, like a parser, avoiding
+    /// repeatedly taking and releasing the lock.
+    ///
+    /// The write lock is held until the returned [`BatchInterner`] is dropped.
+    #[inline]
+    pub fn batch(&'static self) -> BatchInterner {
+        BatchInterner {
+            guard: self.write(),
+        }
+    }
+
+    /// Install a custom allocator for the strings of dynamically created
+    /// symbols.
+    ///
+    /// Symbols that were created before this call keep their existing storage,
+    /// so it is best to call this early in `main()`. See
+    /// [`GlobalSymbolAllocator`] for more details.
+    ///
+    /// # Errors
+    ///
+    /// The allocator can only be set once. If an allocator has already been
+    /// set, `allocator` is returned as an error.
+    #[cfg(feature = "alloc")]
+    pub fn set_allocator(
+        &'static self,
+        allocator: &'static dyn GlobalSymbolAllocator,
+    ) -> Result<(), &'static dyn GlobalSymbolAllocator> {
+        let mut write = self.write();
+        if write.guard.allocator.is_some() {
+            return Err(allocator);
+        }
+        write.guard.allocator = Some(allocator);
+        Ok(())
+    }
+
     /// Resolve and register symbols from a table.
     ///
     /// You should never need to call this function manually.
@@ -147,6 +341,181 @@ impl Registry {
         }
     }
 
+    /// Resolve and register symbols from a table, from normal code.
+    ///
+    /// This is a safe alternative to [`register_sites()`](Self::register_sites)
+    /// for symbol tables that are registered after startup, for example when
+    /// static initializers are not available, or after loading a library with
+    /// `dlopen`. Unlike `register_sites()`, it does not assume that it has
+    /// exclusive access to the table: it takes the registry's write lock for
+    /// the duration of the call (like [`Symbol::new()`] does for a single new
+    /// symbol), so concurrent registrations are serialized, and it accesses
+    /// the call sites atomically. Sites that were already registered are not
+    /// written to again, but they are counted again by
+    /// [`report_startup()`](Self::report_startup).
+    ///
+    /// This is slower than `register_sites()`, which remains the right choice
+    /// in static initializers. Note that evaluating a `sym!()` call site of
+    /// the table before it has been registered is still a bug, see
+    /// [`Site::get_after_ctor()`].
+    pub fn register_sites_safe(&'static self, table: &[Site]) {
+        self.write()
+            .register_sites_with(table, Site::load_string, Site::store_interned);
+    }
+
+    /// Register all of `strings` as symbols, under a single write lock.
+    ///
+    /// This is intended for large, generated tables of known symbols, so that
+    /// [`Symbol::get()`] succeeds for all of them. Like
+    /// [`Symbol::new_static()`], the strings are not copied when they are not
+    /// already registered.
+    ///
+    /// Use the
+    /// [`stringleton::preload_symbols!()`](../stringleton/macro.preload_symbols.html)
+    /// macro to call this from a static initializer.
+    pub fn preload(&'static self, strings: &[&'static &'static str]) {
+        let mut write = self.write();
+        for string in strings {
+            _ = write.get_or_insert_static(string);
+        }
+    }
+
+    /// Handle `sym!()` call sites that are reached before they have been
+    /// initialized, instead of panicking.
+    ///
+    /// With the `debug-assertions` feature, reaching a call site that was not
+    /// initialized by a static constructor panics by default, because it
+    /// usually means that the linker setup is broken (see
+    /// [`Site::get_after_ctor()`]). In advanced dynamic loading setups, this
+    /// can be recoverable. When a handler is installed, it is called with the
+    /// site instead of panicking, for example to log the problem, and the site
+    /// is then resolved through the registry and initialized, like on targets
+    /// without static constructors. The handler is usually called once per
+    /// site, but may be called more than once if several threads reach the
+    /// same site at the same time.
+    ///
+    /// The handler only runs on this cold path. Initialized sites are not
+    /// affected, and without the `debug-assertions` feature, call sites are
+    /// not checked at all.
+    #[cfg(feature = "debug-assertions")]
+    pub fn set_uninitialized_site_handler(&'static self, handler: fn(&Site)) {
+        self.write().guard.uninitialized_site_handler = Some(handler);
+    }
+
+    #[cfg(feature = "debug-assertions")]
+    pub(crate) fn uninitialized_site_handler(&'static self) -> Option<fn(&Site)> {
+        self.read().guard.uninitialized_site_handler
+    }
+
+    /// Get statistics about the registered symbols and call sites.
+    ///
+    /// Call this at the start of `main()` and print the result to see the
+    /// size of the symbol table, for example when deciding between `sym!()`
+    /// and a shared `static_sym!()` to reduce binary size:
+    ///
+    /// ```rust,ignore
+    /// eprintln!("{}", Registry::global().report_startup());
+    /// // 1234 call sites (9872 bytes), 567 symbols (8910 bytes of strings)
+    /// ```
+    ///
+    /// The call sites are the ones registered by static initializers of all
+    /// crates in the process, so the count is always zero on targets without
+    /// static initializers (Miri and `wasm32`). This takes the read lock, and
+    /// visits all symbols to sum up their lengths.
+    #[must_use]
+    pub fn report_startup(&'static self) -> StartupReport {
+        let read = self.read();
+        StartupReport {
+            sites: read.guard.site_count,
+            site_bytes: read.guard.site_count * size_of::<Site>(),
+            symbols: read.len(),
+            string_bytes: read.iter().map(|symbol| symbol.len()).sum(),
+        }
+    }
+
+    /// Get the addresses of all `sym!()` and `static_sym!()` call sites that
+    /// resolved to `symbol`.
+    ///
+    /// This is a debugging aid, for example to find out which code paths use a
+    /// surprising symbol. The addresses can be compared with the addresses of
+    /// statics in a debugger, but are not generally safe to dereference. Call
+    /// sites are recorded when they are registered by a static initializer, so
+    /// the result is always empty on targets without static initializers (Miri
+    /// and `wasm32`).
+    ///
+    /// This is a linear scan over all call sites in the program.
+    #[cfg(feature = "introspection")]
+    #[must_use]
+    pub fn sites_for(&'static self, symbol: Symbol) -> Vec<*const Site> {
+        let address = symbol.as_ptr_usize();
+        self.read()
+            .guard
+            .sites
+            .iter()
+            .filter(|&&(_, symbol)| symbol == address)
+            .map(|&(site, _)| core::ptr::with_exposed_provenance(site))
+            .collect()
+    }
+
+    /// Get the `file!()` and `line!()` of the `sym!()` or `static_sym!()` call
+    /// site where `symbol` was first declared.
+    ///
+    /// This is a debugging aid, for example to find out why two code paths
+    /// produce different symbols. "First" means the first call site to be
+    /// registered, which depends on the link order of the crates in the
+    /// program, not on the order of execution. Returns `None` for symbols that
+    /// were only created at runtime, and always on targets without static
+    /// initializers (Miri and `wasm32`).
+    #[cfg(feature = "track-caller-sites")]
+    #[must_use]
+    pub fn site_location(&'static self, symbol: Symbol) -> Option<(&'static str, u32)> {
+        self.read()
+            .guard
+            .locations
+            .get(&symbol.as_ptr_usize())
+            .copied()
+    }
+
+    /// Get the number of lookups that found an existing symbol under the read
+    /// lock, and of new symbols inserted under the write lock, since startup
+    /// or the last call to [`reset_access_stats()`](Self::reset_access_stats).
+    ///
+    /// This is a profiling aid to measure lock pressure: every interning of a
+    /// runtime string through [`get_or_insert()`](Self::get_or_insert) (and
+    /// so [`Symbol::new()`] and [`Symbol::try_new()`]) that does not count as
+    /// a read hit took the write lock. Lookups that race with an insertion of
+    /// the same string take the write lock without inserting, so they are not
+    /// counted at all. `sym!()` call sites do not go through this path.
+    ///
+    /// The counters are relaxed atomics, so the two numbers are not a
+    /// consistent snapshot while other threads are creating symbols.
+    #[cfg(feature = "profiling")]
+    #[must_use]
+    pub fn access_stats(&'static self) -> AccessStats {
+        AccessStats {
+            read_hits: self
+                .counters
+                .read_hits
+                .load(core::sync::atomic::Ordering::Relaxed),
+            write_inserts: self
+                .counters
+                .write_inserts
+                .load(core::sync::atomic::Ordering::Relaxed),
+        }
+    }
+
+    /// Reset the counters returned by [`access_stats()`](Self::access_stats)
+    /// to zero, for example to exclude startup from a measurement.
+    #[cfg(feature = "profiling")]
+    pub fn reset_access_stats(&'static self) {
+        self.counters
+            .read_hits
+            .store(0, core::sync::atomic::Ordering::Relaxed);
+        self.counters
+            .write_inserts
+            .store(0, core::sync::atomic::Ordering::Relaxed);
+    }
+
     /// Check if the registry contains a symbol matching `string` and return it
     /// if so.
     #[must_use]
@@ -155,6 +524,13 @@ impl Registry {
         self.read().guard.get(string)
     }
 
+    /// Check if the registry contains a symbol matching `string`.
+    #[must_use]
+    #[inline]
+    pub fn contains(&'static self, string: &str) -> bool {
+        self.read().contains(string)
+    }
+
     /// Get the existing symbol for `string`, or insert a new one.
     ///
     /// This opportunistically takes a read lock to check if the symbol exists,
@@ -166,13 +542,102 @@ impl Registry {
     #[cfg(any(feature = "std", feature = "alloc"))]
     #[must_use]
     pub fn get_or_insert(&'static self, string: &str) -> Symbol {
+        self.get_or_insert_reporting(string).0
+    }
+
+    /// Get the existing symbol for `string`, or insert a new one, and report
+    /// whether it was inserted.
+    ///
+    /// The returned `bool` is `true` if this call created the symbol, and
+    /// `false` if it already existed. Otherwise, this is the same as
+    /// [`get_or_insert()`](Self::get_or_insert).
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn get_or_insert_reporting(&'static self, string: &str) -> (Symbol, bool) {
         let read = self.read();
         if let Some(previously_interned) = read.get(string) {
-            return previously_interned;
+            #[cfg(feature = "profiling")]
+            self.counters.read_hit();
+            return (previously_interned, false);
+        }
+        core::mem::drop(read);
+        let (symbol, inserted) = self.write().guard.get_or_insert_reporting(string);
+        #[cfg(feature = "profiling")]
+        self.counters.write_insert(inserted);
+        (symbol, inserted)
+    }
+
+    /// Get the existing symbol for `string`, or insert a new one if it is
+    /// within the limits set for this registry.
+    ///
+    /// This is the checked version of [`get_or_insert()`](Self::get_or_insert),
+    /// used by [`Symbol::try_new()`]. Existing symbols are always returned,
+    /// even if they exceed the limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InternError::SymbolTooLong`] if the symbol does not exist, and
+    /// `string` is longer than the maximum set by
+    /// [`set_max_symbol_len()`](Self::set_max_symbol_len). Nothing is
+    /// allocated in that case.
+    #[cfg(feature = "alloc")]
+    pub fn try_get_or_insert(&'static self, string: &str) -> Result<Symbol, InternError> {
+        let read = self.read();
+        if let Some(previously_interned) = read.get(string) {
+            #[cfg(feature = "profiling")]
+            self.counters.read_hit();
+            return Ok(previously_interned);
         }
         core::mem::drop(read);
         let mut write = self.write();
-        write.get_or_insert(string)
+        if let Some(max) = write.guard.max_len
+            && string.len() > max
+            && write.get(string).is_none()
+        {
+            return Err(InternError::SymbolTooLong {
+                len: string.len(),
+                max,
+            });
+        }
+        #[cfg_attr(not(feature = "profiling"), allow(unused_variables))]
+        let (symbol, inserted) = write.guard.get_or_insert_reporting(string);
+        #[cfg(feature = "profiling")]
+        self.counters.write_insert(inserted);
+        Ok(symbol)
+    }
+
+    /// Set the maximum length in bytes of new symbols created through
+    /// [`Symbol::try_new()`] and [`try_get_or_insert()`](Self::try_get_or_insert).
+    ///
+    /// This limits how much memory a single untrusted string can leak. Other
+    /// ways of creating symbols, like [`Symbol::new()`] and `sym!()`, are not
+    /// limited, and existing symbols that are longer than `max` can still be
+    /// looked up.
+    #[cfg(feature = "alloc")]
+    pub fn set_max_symbol_len(&'static self, max: usize) {
+        self.write().guard.max_len = Some(max);
+    }
+
+    /// Get the existing symbol for `string`, or insert a new one, taking
+    /// ownership of the allocation.
+    ///
+    /// If this call inserted the symbol, `string` is leaked and used as the
+    /// storage of the symbol, so the string is not copied. Otherwise, `string`
+    /// is dropped. When a custom allocator has been installed with
+    /// [`set_allocator()`](Self::set_allocator), the string is copied into it
+    /// as usual.
+    ///
+    /// This opportunistically takes a read lock to check if the symbol exists,
+    /// and only takes a write lock if it doesn't.
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn get_or_insert_boxed(&'static self, string: Box<str>) -> Symbol {
+        let read = self.read();
+        if let Some(previously_interned) = read.get(&string) {
+            return previously_interned;
+        }
+        core::mem::drop(read);
+        self.write().guard.get_or_insert_boxed(string)
     }
 
     /// Get the existing symbol for `string`, or insert a new one.
@@ -202,6 +667,63 @@ impl Registry {
         write.get_or_insert_static(string)
     }
 
+    /// Get the number of symbols for which the registry has allocated (and
+    /// leaked) memory.
+    ///
+    /// Symbols are never freed, so memory leak detectors report the memory of
+    /// every symbol created from a dynamic string. Tests can compare this
+    /// counter before and after running some code to check that it creates
+    /// exactly the expected number of new symbols, telling intended symbol
+    /// leaks apart from accidental ones.
+    ///
+    /// Symbols that are backed by static strings (`sym!()`, `static_sym!()`,
+    /// and [`Symbol::new_static()`]) are not counted. Allocations made through
+    /// a custom allocator installed with [`set_allocator()`](Self::set_allocator)
+    /// are counted.
+    ///
+    /// This takes a read lock.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn leaked_count(&'static self) -> usize {
+        self.read().guard.leaked_count
+    }
+
+    /// Get the number of bytes the registry has allocated (and leaked) for
+    /// symbols, including the strings and the references to them. See
+    /// [`leaked_count()`](Self::leaked_count).
+    ///
+    /// This takes a read lock.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn leaked_bytes(&'static self) -> usize {
+        self.read().guard.leaked_bytes
+    }
+
+    /// Get the existing symbol for `string`, or insert a new one backed by
+    /// `string`.
+    ///
+    /// This is the same as [`get_or_insert_static()`](Self::get_or_insert_static),
+    /// for callers that only have a `&'static str` (e.g. from
+    /// [`String::leak()`](alloc::string::String::leak)). The string is not
+    /// copied, but a new symbol needs a `&'static str` to point to, so one
+    /// pointer is allocated (and leaked) if this call inserted the symbol.
+    ///
+    /// This opportunistically takes a read lock to check if the symbol exists,
+    /// and only takes a write lock if it doesn't.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn get_or_insert_static_str(&'static self, string: &'static str) -> Symbol {
+        let read = self.read();
+        if let Some(previously_interned) = read.get(string) {
+            return previously_interned;
+        }
+        core::mem::drop(read);
+        self.write().guard.get_or_insert_static_str(string)
+    }
+
     /// Check if a symbol has been registered at `address` (i.e., it has been
     /// produced by [`Symbol::to_ffi()`]), and return the symbol if so.
     ///
@@ -212,26 +734,436 @@ impl Registry {
     pub fn get_by_address(&'static self, address: u64) -> Option<Symbol> {
         self.read().get_by_address(address)
     }
+
+    /// Check if a symbol has been registered at `ptr` (i.e., it is the address
+    /// returned by [`Symbol::as_ptr()`]), and return the symbol if so.
+    ///
+    /// This is the same as [`get_by_address()`](Self::get_by_address), for
+    /// code that has a typed pointer instead of an FFI handle. `ptr` is never
+    /// dereferenced, only its address is looked up, so any pointer value
+    /// (including null or dangling pointers) is accepted.
+    #[inline]
+    #[must_use]
+    pub fn get_by_ptr(&'static self, ptr: *const &'static str) -> Option<Symbol> {
+        self.read().get_by_ptr(ptr)
+    }
+
+    /// Resolve a batch of FFI handles (produced by [`Symbol::to_ffi()`]) under a
+    /// single read lock.
+    ///
+    /// Each element of `out` is set to the symbol for the corresponding handle
+    /// in `handles`, or `None` if the handle is not a registered symbol. Returns
+    /// the number of handles that were successfully resolved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handles` and `out` have different lengths.
+    pub fn try_from_ffi_slice(&'static self, handles: &[u64], out: &mut [Option<Symbol>]) -> usize {
+        assert_eq!(
+            handles.len(),
+            out.len(),
+            "`handles` and `out` must have the same length"
+        );
+        let read = self.read();
+        let mut resolved = 0;
+        for (handle, out) in handles.iter().zip(out) {
+            *out = read.get_by_address(*handle);
+            resolved += usize::from(out.is_some());
+        }
+        resolved
+    }
+
+    /// Check that all of `names` have been registered as symbols.
+    ///
+    /// This is intended for applications with a required vocabulary of symbols,
+    /// for example after loading all plugins, so that missing symbols become a
+    /// clear error at startup. This takes the read lock once, and never creates
+    /// new symbols.
+    ///
+    /// # Errors
+    ///
+    /// If any names are not registered, they are returned in the error, in the
+    /// order they appear in `names`.
+    #[cfg(feature = "alloc")]
+    pub fn require(&'static self, names: &[&str]) -> Result<(), MissingSymbols> {
+        let read = self.read();
+        let missing: Vec<String> = names
+            .iter()
+            .filter(|name| read.get(name).is_none())
+            .map(|&name| name.to_owned())
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(MissingSymbols { names: missing })
+        }
+    }
+}
+
+impl Registry {
+    /// Find pairs of distinct symbols that are equal when ignoring ASCII case,
+    /// ASCII whitespace, `_`, and `-`.
+    ///
+    /// For example, `"renderOrder"`, `"render_order"`, and `"Render Order"`
+    /// are all reported as near-duplicates of each other. This is intended to
+    /// catch typos in large symbol sets, for example from a test or a debug
+    /// command. It takes the read lock for the duration of the scan, which is
+    /// O(n log n) in the number of registered symbols.
+    ///
+    /// The result is deterministic for a given set of registered symbols: each
+    /// pair is ordered by string, and pairs are sorted by their normalized
+    /// string.
+    #[cfg(feature = "diagnostics")]
+    #[must_use]
+    pub fn find_near_duplicates(&'static self) -> Vec<(Symbol, Symbol)> {
+        fn normalize(string: &str) -> String {
+            string
+                .chars()
+                .filter(|&c| !c.is_ascii_whitespace() && c != '_' && c != '-')
+                .map(|c| c.to_ascii_lowercase())
+                .collect()
+        }
+
+        let mut normalized: Vec<(String, Symbol)> = self
+            .read()
+            .iter()
+            .map(|symbol| (normalize(symbol.as_str()), symbol))
+            .collect();
+        normalized.sort_unstable_by(|(a_norm, a), (b_norm, b)| {
+            a_norm.cmp(b_norm).then_with(|| a.as_str().cmp(b.as_str()))
+        });
+
+        let mut pairs = Vec::new();
+        for group in normalized.chunk_by(|(a, _), (b, _)| a == b) {
+            for (i, (_, a)) in group.iter().enumerate() {
+                for (_, b) in &group[i + 1..] {
+                    pairs.push((*a, *b));
+                }
+            }
+        }
+        pairs
+    }
+}
+
+impl RegistryReadGuard {
+    /// Count the registered symbols by the length of their strings in bytes.
+    ///
+    /// Bucket `n` holds the number of symbols that are exactly `n` bytes long,
+    /// except the last bucket, which holds all symbols of 63 bytes or more. The
+    /// empty string is counted in bucket 0.
+    ///
+    /// This is a profiling aid, for example to choose a limit for
+    /// [`Registry::set_max_symbol_len()`], and visits every registered symbol.
+    #[cfg(feature = "diagnostics")]
+    #[must_use]
+    pub fn length_histogram(&self) -> [usize; 64] {
+        let mut histogram = [0; 64];
+        for string in self.guard.by_string.keys() {
+            histogram[string.0.len().min(63)] += 1;
+        }
+        histogram
+    }
+}
+
+impl Registry {
+    /// Register the symbols of a table previously produced by
+    /// [`RegistryReadGuard::export_table()`], and reassign symbol IDs to match
+    /// the table.
+    ///
+    /// After this call, [`Symbol::id()`] and [`Symbol::from_id()`] agree with
+    /// the IDs in `entries`, so the table can be used as a persistent symbol
+    /// dictionary (for example in save files). Symbols that are registered but
+    /// not in `entries` get the IDs following the table, in their previous
+    /// order.
+    ///
+    /// Only the ID mapping is restored. Symbol pointers (and therefore hashes
+    /// and FFI handles) are never stable between processes.
+    ///
+    /// This must be called early, before any IDs have been observed, because
+    /// existing symbols (including all `sym!()` symbols) may be assigned new
+    /// IDs. IDs are observed through [`Symbol::id()`], [`Symbol::from_id()`],
+    /// [`RegistryReadGuard::id_of()`], [`RegistryReadGuard::get_by_id()`], and
+    /// [`RegistryReadGuard::export_table()`], and by anything that uses them,
+    /// like [`SymbolBitSet`](crate::SymbolBitSet).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the IDs in `entries` are not exactly `0..entries.len()` (in
+    /// any order), if a string appears more than once, or if IDs have already
+    /// been observed. Symbol IDs are not changed in that case.
+    #[cfg(feature = "alloc")]
+    pub fn import_table<S: AsRef<str>>(&'static self, entries: &[(u32, S)]) {
+        let mut strings: Vec<Option<&str>> = vec![None; entries.len()];
+        for (id, string) in entries {
+            let slot = strings
+                .get_mut(*id as usize)
+                .expect("symbol table IDs must be contiguous, starting at 0");
+            assert!(slot.is_none(), "duplicate ID in symbol table: {id}");
+            *slot = Some(string.as_ref());
+        }
+
+        let mut write = self.write();
+        assert!(
+            !write
+                .guard
+                .ids_observed
+                .load(core::sync::atomic::Ordering::Relaxed),
+            "symbol table imported after symbol IDs were observed"
+        );
+        let order: Vec<SymbolStr> = strings
+            .into_iter()
+            .map(|string| {
+                // All slots are filled, because the IDs are unique and in range.
+                let symbol = write.get_or_insert(string.unwrap());
+                SymbolStr(symbol.inner())
+            })
+            .collect();
+        write.guard.reorder(&order);
+    }
+}
+
+/// Statistics returned by [`Registry::report_startup()`].
+///
+/// The `Display` implementation prints a one-line summary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StartupReport {
+    /// The number of `sym!()` and `static_sym!()` call sites.
+    pub sites: usize,
+    /// The size of the call sites in bytes, which are stored in the `.bss`
+    /// segment of the binary.
+    pub site_bytes: usize,
+    /// The number of registered symbols.
+    pub symbols: usize,
+    /// The total length of the strings of all symbols in bytes.
+    pub string_bytes: usize,
+}
+
+impl core::fmt::Display for StartupReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} call sites ({} bytes), {} symbols ({} bytes of strings)",
+            self.sites, self.site_bytes, self.symbols, self.string_bytes
+        )
+    }
+}
+
+/// Counters returned by [`Registry::access_stats()`].
+#[cfg(feature = "profiling")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AccessStats {
+    /// The number of lookups that found an existing symbol under the read
+    /// lock.
+    pub read_hits: usize,
+    /// The number of symbols inserted under the write lock.
+    pub write_inserts: usize,
+}
+
+/// Error returned by [`Registry::require()`], listing the names that have not
+/// been registered as symbols.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingSymbols {
+    names: Vec<String>,
+}
+
+#[cfg(feature = "alloc")]
+impl MissingSymbols {
+    /// The names that were not registered.
+    #[inline]
+    #[must_use]
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for MissingSymbols {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("missing required symbols: ")?;
+        for (i, name) in self.names.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{name:?}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for MissingSymbols {}
+
+/// Error returned when a symbol cannot be created, because it would exceed a
+/// limit of the registry.
+///
+/// Returned by [`Symbol::try_new()`] and [`Registry::try_get_or_insert()`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InternError {
+    /// The string is longer than the maximum set by
+    /// [`Registry::set_max_symbol_len()`].
+    SymbolTooLong {
+        /// The length of the string in bytes.
+        len: usize,
+        /// The maximum length in bytes.
+        max: usize,
+    },
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for InternError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            InternError::SymbolTooLong { len, max } => {
+                write!(f, "symbol is too long ({len} bytes, the maximum is {max})")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for InternError {}
+
+/// Error returned by [`Symbol::try_new_borrowed()`] when the string has not
+/// been registered as a symbol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NotInterned;
+
+impl core::fmt::Display for NotInterned {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("string is not an interned symbol")
+    }
 }
 
+impl core::error::Error for NotInterned {}
+
 impl Store {
+    fn capacity(&self) -> usize {
+        self.by_string
+            .capacity()
+            .min(self.by_pointer.capacity())
+            .min(self.order.capacity())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.by_string.reserve(additional);
+        self.by_pointer.reserve(additional);
+        self.order.reserve(additional);
+    }
+
+    /// Get the ID of the next symbol, and reserve space for it in `by_pointer`
+    /// and `order`, so that `push_reserved()` cannot panic.
+    ///
+    /// Panics from the allocator or in here leave the store unchanged, which
+    /// is important because the locks ignore poisoning. This must be called
+    /// before inserting into `by_string`, so that the maps never get out of
+    /// sync.
+    fn reserve_id(by_pointer: &mut HashMap<usize, u32>, order: &mut Vec<SymbolStr>) -> u32 {
+        let id = u32::try_from(order.len()).expect("too many symbols");
+        by_pointer.reserve(1);
+        order.reserve(1);
+        id
+    }
+
+    fn push_reserved(&mut self, key: SymbolStr, id: u32) {
+        self.by_pointer.insert(key.address(), id);
+        self.order.push(key);
+    }
+
     #[cfg(any(feature = "std", feature = "alloc"))]
     pub fn get_or_insert(&mut self, string: &str) -> Symbol {
-        let entry;
-        match self.by_string.entry_ref(string) {
-            hash_map::EntryRef::Occupied(e) => entry = e,
-            hash_map::EntryRef::Vacant(e) => {
-                // This calls `SymbolStr::from(string)`, which does the leaking.
-                entry = e.insert_entry(());
-                let interned = entry.key();
-                self.by_pointer.insert(interned.address(), *interned);
-            }
+        self.get_or_insert_reporting(string).0
+    }
+
+    /// Returns the symbol, and whether it was inserted.
+    #[cfg(feature = "alloc")]
+    pub fn get_or_insert_reporting(&mut self, string: &str) -> (Symbol, bool) {
+        let (symbol, inserted) =
+            self.get_or_insert_with(string, |allocator| allocator.alloc_str(string));
+        if inserted {
+            self.record_leak(string.len() + size_of::<&str>());
         }
+        (symbol, inserted)
+    }
 
-        unsafe {
+    /// Like `get_or_insert()`, but uses `string` itself as the storage of a
+    /// new symbol, so only the reference to it is allocated.
+    #[cfg(feature = "alloc")]
+    pub fn get_or_insert_static_str(&mut self, string: &'static str) -> Symbol {
+        let (symbol, inserted) = self.get_or_insert_with(string, |_| string);
+        if inserted {
+            self.record_leak(size_of::<&str>());
+        }
+        symbol
+    }
+
+    #[cfg(feature = "alloc")]
+    fn record_leak(&mut self, bytes: usize) {
+        self.leaked_count += 1;
+        self.leaked_bytes += bytes;
+    }
+
+    /// Get the symbol for `string`, or insert the string returned by `alloc`
+    /// as a new symbol. Returns the symbol, and whether it was inserted.
+    #[cfg(feature = "alloc")]
+    fn get_or_insert_with(
+        &mut self,
+        string: &str,
+        alloc: impl FnOnce(&dyn GlobalSymbolAllocator) -> &'static str,
+    ) -> (Symbol, bool) {
+        let (interned, inserted) = match self.by_string.entry_ref(string) {
+            hash_map::EntryRef::Occupied(entry) => (*entry.key(), false),
+            hash_map::EntryRef::Vacant(entry) => {
+                let id = Self::reserve_id(&mut self.by_pointer, &mut self.order);
+                let allocator = self.allocator.unwrap_or(&LeakingSymbolAllocator);
+                let key = SymbolStr(allocator.alloc_str_ref(alloc(allocator)));
+                entry.insert_with_key(key, ());
+                self.push_reserved(key, id);
+                (key, true)
+            }
+        };
+
+        let symbol = unsafe {
             // SAFETY: We are the registry.
-            Symbol::new_unchecked(entry.key().0)
+            Symbol::new_unchecked(interned.0)
+        };
+        (symbol, inserted)
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn get_or_insert_boxed(&mut self, string: Box<str>) -> Symbol {
+        // Without a custom allocator, the box becomes the storage of a new
+        // symbol. It is only freed after the lookup, so the entry API can
+        // borrow it while it may still be moved into the registry.
+        let reuse = self.allocator.is_none();
+        let raw = Box::into_raw(string);
+        let borrowed: &'static str = unsafe {
+            // SAFETY: `raw` is valid until it is freed below, and this
+            // reference is only kept beyond that if the box is never freed.
+            &*raw
+        };
+        let (symbol, inserted) = self.get_or_insert_with(borrowed, |allocator| {
+            if reuse {
+                borrowed
+            } else {
+                allocator.alloc_str(borrowed)
+            }
+        });
+        if inserted {
+            self.record_leak(borrowed.len() + size_of::<&str>());
+        }
+        if !(inserted && reuse) {
+            core::mem::drop(unsafe {
+                // SAFETY: The string was not moved into the registry, and
+                // `borrowed` is not used after this.
+                Box::from_raw(raw)
+            });
         }
+        symbol
     }
 
     /// Fast-path for `&'static &'static str` without needing to allocate and
@@ -244,8 +1176,9 @@ impl Store {
         let interned = match self.by_string.entry(symstr) {
             hash_map::Entry::Occupied(entry) => *entry.key(), // Getting the original key.
             hash_map::Entry::Vacant(entry) => {
+                let id = Self::reserve_id(&mut self.by_pointer, &mut self.order);
                 let key = *entry.insert_entry(()).key();
-                self.by_pointer.insert(key.address(), key);
+                self.push_reserved(key, id);
                 key
             }
         };
@@ -256,6 +1189,10 @@ impl Store {
         }
     }
 
+    pub fn contains(&self, string: &str) -> bool {
+        self.by_string.contains_key(string)
+    }
+
     pub fn get(&self, string: &str) -> Option<Symbol> {
         self.by_string
             .get_key_value(string)
@@ -265,15 +1202,80 @@ impl Store {
             })
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.by_string.keys().map(|symstr| unsafe {
+            // SAFETY: We are the registry.
+            Symbol::new_unchecked(symstr.0)
+        })
+    }
+
+    pub fn iter_ordered(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.order.iter().map(|symstr| unsafe {
+            // SAFETY: We are the registry.
+            Symbol::new_unchecked(symstr.0)
+        })
+    }
+
     #[allow(clippy::cast_possible_truncation)] // We don't have 128-bit pointers
     pub fn get_by_address(&self, address: u64) -> Option<Symbol> {
         self.by_pointer
             .get(&(address as usize))
+            .and_then(|&id| self.order.get(id as usize))
             .map(|symstr| unsafe {
                 // SAFETY: We are the registry.
                 Symbol::new_unchecked(symstr.0)
             })
     }
+
+    pub fn get_by_id(&self, id: u32) -> Option<Symbol> {
+        self.observe_ids();
+        self.order.get(id as usize).map(|symstr| unsafe {
+            // SAFETY: We are the registry.
+            Symbol::new_unchecked(symstr.0)
+        })
+    }
+
+    pub fn id_of(&self, symbol: Symbol) -> Option<u32> {
+        self.observe_ids();
+        self.by_pointer.get(&symbol.as_ptr_usize()).copied()
+    }
+
+    /// Record that IDs have been handed out, so they can no longer be
+    /// reassigned.
+    #[inline]
+    pub fn observe_ids(&self) {
+        #[cfg(feature = "alloc")]
+        self.ids_observed
+            .store(true, core::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Reassign IDs so that each symbol in `first` gets its index as its ID.
+    /// All other symbols get the following IDs, in their previous order.
+    ///
+    /// Panics if `first` contains duplicates, before changing anything.
+    #[cfg(feature = "alloc")]
+    fn reorder(&mut self, first: &[SymbolStr]) {
+        let mut taken = vec![false; self.order.len()];
+        for key in first {
+            let old_id = self.by_pointer[&key.address()] as usize;
+            assert!(
+                !core::mem::replace(&mut taken[old_id], true),
+                "duplicate string in symbol table: {:?}",
+                key.0
+            );
+        }
+        let rest = self
+            .order
+            .iter()
+            .zip(taken)
+            .filter(|&(_, taken)| !taken)
+            .map(|(key, _)| *key);
+        self.order = first.iter().copied().chain(rest).collect();
+        for (id, key) in self.order.iter().enumerate() {
+            #[allow(clippy::cast_possible_truncation)] // Checked in `reserve_id()`
+            self.by_pointer.insert(key.address(), id as u32);
+        }
+    }
 }
 
 impl RegistryReadGuard {
@@ -291,6 +1293,14 @@ impl RegistryReadGuard {
         self.guard.by_string.is_empty()
     }
 
+    /// Get the number of symbols the registry can hold without growing its
+    /// hash tables. See [`Registry::init_global_with_capacity()`].
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.guard.capacity()
+    }
+
     /// Check if the registry contains a symbol matching `string` and return it
     /// if so.
     ///
@@ -301,6 +1311,34 @@ impl RegistryReadGuard {
         self.guard.get(string)
     }
 
+    /// Check if the registry contains a symbol matching `string`.
+    ///
+    /// This is the same hash table lookup as [`get()`](Self::get), for when
+    /// the symbol itself is not needed.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, string: &str) -> bool {
+        self.guard.contains(string)
+    }
+
+    /// Check if the registry contains a symbol matching `string` and return
+    /// the interned string if so.
+    ///
+    /// This canonicalizes `string` against the registry without creating a
+    /// [`Symbol`] and without allocating, which also works in `no_std`
+    /// environments without `alloc`. The returned string is equal to `string`,
+    /// and is the same string slice for all equal inputs.
+    ///
+    /// The returned reference borrows the guard, so the read lock is held for
+    /// as long as it is in use. The storage of a symbol is never freed, so
+    /// use [`get()`](Self::get) and [`Symbol::as_str()`] to obtain a
+    /// `&'static str` that outlives the guard.
+    #[inline]
+    #[must_use]
+    pub fn get_str(&self, string: &str) -> Option<&str> {
+        self.guard.get(string).map(|symbol| symbol.as_str())
+    }
+
     /// Check if a symbol has been registered at `address` (i.e., it has been
     /// produced by [`Symbol::to_ffi()`]), and return the symbol if so.
     ///
@@ -311,18 +1349,136 @@ impl RegistryReadGuard {
     pub fn get_by_address(&self, address: u64) -> Option<Symbol> {
         self.guard.get_by_address(address)
     }
+
+    /// Check if a symbol has been registered at `ptr`, and return the symbol
+    /// if so. See [`Registry::get_by_ptr()`].
+    #[inline]
+    #[must_use]
+    pub fn get_by_ptr(&self, ptr: *const &'static str) -> Option<Symbol> {
+        self.guard.get_by_address(ptr.addr() as u64)
+    }
+
+    /// Iterate over all registered symbols.
+    ///
+    /// The iteration order is unspecified.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.guard.iter()
+    }
+
+    /// Iterate over all registered symbols in the order of their IDs, which is
+    /// the order they were first registered (see [`Symbol::id()`]).
+    ///
+    /// The same sequence of registrations always produces the same order,
+    /// which makes this suitable for writing a reproducible symbol table.
+    /// Note that symbols from `sym!()` call sites are registered when each
+    /// binary or dynamic library is loaded, in link order.
+    #[inline]
+    pub fn iter_ordered(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.guard.iter_ordered()
+    }
+
+    /// Get the symbol with the ID `id`, if any. See [`Symbol::id()`].
+    #[inline]
+    #[must_use]
+    pub fn get_by_id(&self, id: u32) -> Option<Symbol> {
+        self.guard.get_by_id(id)
+    }
+
+    /// Get the ID of `symbol`. See [`Symbol::id()`].
+    ///
+    /// This only returns `None` for symbols that were not created through the
+    /// registry.
+    #[inline]
+    #[must_use]
+    pub fn id_of(&self, symbol: Symbol) -> Option<u32> {
+        self.guard.id_of(symbol)
+    }
+
+    /// Export all registered symbols with their IDs, ordered by ID.
+    ///
+    /// The table can be persisted and passed to [`Registry::import_table()`]
+    /// in another process, to restore the same ID for each string.
+    #[must_use]
+    pub fn export_table(&self) -> Vec<(u32, &'static str)> {
+        self.guard.observe_ids();
+        self.iter_ordered()
+            .zip(0..)
+            .map(|(symbol, id)| (id, symbol.as_str()))
+            .collect()
+    }
+
+    /// Get the strings of all registered symbols, sorted lexically.
+    ///
+    /// Unlike [`iter()`](Self::iter), the result does not depend on hash order
+    /// or registration order, so it is a stable snapshot for golden tests and
+    /// diagnostics, for example to check which symbols a dynamic library
+    /// contributed to the shared registry.
+    ///
+    /// This collects all strings and sorts them, so it is O(n log n).
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn sorted_strings(&self) -> Vec<&'static str> {
+        let mut strings: Vec<&'static str> = self.iter().map(|symbol| symbol.as_str()).collect();
+        strings.sort_unstable();
+        strings
+    }
+
+    /// Iterate over all registered symbols that start with `prefix`, e.g. for
+    /// autocompletion.
+    ///
+    /// The iteration order is unspecified.
+    ///
+    /// This is a linear scan over all symbols in the registry, so it is O(n)
+    /// in the total number of symbols. New symbols cannot be created while the
+    /// read lock is held, so avoid holding on to the iterator for long.
+    #[inline]
+    pub fn iter_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = Symbol> + 'a {
+        self.iter()
+            .filter(move |symbol| symbol.as_str().starts_with(prefix))
+    }
 }
 
 impl RegistryWriteGuard {
     unsafe fn register_sites(&mut self, sites: &[Site]) {
-        unsafe {
-            for registration in sites {
-                let string = registration.get_string();
-                let interned = self.guard.get_or_insert_static(string);
-                // Place the interned string pointer at the site and mark it as
-                // initialized.
-                registration.initialize(interned);
+        self.register_sites_with(
+            sites,
+            |site| unsafe {
+                // SAFETY: Precondition of this function.
+                site.get_string()
+            },
+            |site, interned| unsafe {
+                // SAFETY: Precondition of this function.
+                site.initialize(interned);
+            },
+        );
+    }
+
+    fn register_sites_with(
+        &mut self,
+        sites: &[Site],
+        get_string: impl Fn(&Site) -> &'static &'static str,
+        initialize: impl Fn(&Site, Symbol),
+    ) {
+        self.guard.site_count += sites.len();
+        for registration in sites {
+            let string = get_string(registration);
+            let interned = self.guard.get_or_insert_static(string);
+            #[cfg(feature = "introspection")]
+            {
+                let site = core::ptr::from_ref(registration).expose_provenance();
+                self.guard.sites.push((site, interned.as_ptr_usize()));
             }
+            #[cfg(feature = "track-caller-sites")]
+            if let Some(location) = registration.location() {
+                self.guard
+                    .locations
+                    .entry(interned.as_ptr_usize())
+                    .or_insert(location);
+            }
+            // Place the interned string pointer at the site and mark it as
+            // initialized.
+            initialize(registration, interned);
         }
     }
 
@@ -378,3 +1534,105 @@ impl RegistryWriteGuard {
         self.guard.get_or_insert_static(string)
     }
 }
+
+impl BatchInterner {
+    /// Deserialize a sequence of symbols, interning all of them under this
+    /// batch's write lock.
+    ///
+    /// The returned value implements `serde::de::DeserializeSeed`, producing a
+    /// `Vec<Symbol>`. `&mut BatchInterner` itself implements `DeserializeSeed`
+    /// for a single symbol, which can be used in custom `Visitor`s.
+    ///
+    /// **CAUTION:** The write lock is held during deserialization, so the
+    /// deserialized data must not contain anything that uses the registry by
+    /// other means (like the normal `Deserialize` implementation of
+    /// [`Symbol`]), which would deadlock.
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    #[inline]
+    pub fn seq(&mut self) -> SymbolSeq<'_> {
+        SymbolSeq(self)
+    }
+
+    /// Get the existing symbol for `string`, or insert a new one.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn intern(&mut self, string: &str) -> Symbol {
+        self.guard.get_or_insert(string)
+    }
+
+    /// Get the existing symbol for `string`, or insert a new one, reusing the
+    /// storage of `string` if it is inserted.
+    ///
+    /// See [`Registry::get_or_insert_static()`].
+    #[inline]
+    #[must_use]
+    pub fn intern_static(&mut self, string: &'static &'static str) -> Symbol {
+        self.guard.get_or_insert_static(string)
+    }
+}
+
+/// `serde::de::DeserializeSeed` for a sequence of symbols, interned through a
+/// [`BatchInterner`].
+///
+/// Created by [`BatchInterner::seq()`].
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub struct SymbolSeq<'a>(&'a mut BatchInterner);
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+const _: () = {
+    use core::fmt;
+    use serde::de::{self, DeserializeSeed, SeqAccess, Visitor};
+
+    struct SymbolVisitor<'a>(&'a mut BatchInterner);
+
+    impl Visitor<'_> for SymbolVisitor<'_> {
+        type Value = Symbol;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a string")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Symbol, E> {
+            Ok(self.0.intern(v))
+        }
+    }
+
+    impl<'de> DeserializeSeed<'de> for &mut BatchInterner {
+        type Value = Symbol;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Symbol, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_str(SymbolVisitor(self))
+        }
+    }
+
+    impl<'de> Visitor<'de> for SymbolSeq<'_> {
+        type Value = Vec<Symbol>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a sequence of strings")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<Symbol>, A::Error> {
+            let mut symbols = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+            while let Some(symbol) = seq.next_element_seed(&mut *self.0)? {
+                symbols.push(symbol);
+            }
+            Ok(symbols)
+        }
+    }
+
+    impl<'de> DeserializeSeed<'de> for SymbolSeq<'_> {
+        type Value = Vec<Symbol>;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Vec<Symbol>, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(self)
+        }
+    }
+};
@@ -0,0 +1,174 @@
+use core::{cell::RefCell, hash::Hash};
+
+use alloc::boxed::Box;
+use hashbrown::HashSet;
+
+/// Local string interner whose symbols are freed when it is dropped.
+///
+/// The global [`Registry`](crate::Registry) never frees anything, which is a
+/// problem for transient sets of strings, like the names in a level that is
+/// loaded and later unloaded again. A `ScopedPool` owns its strings, and frees
+/// all of them when it is dropped or [cleared](Self::clear).
+///
+/// Symbols produced by this pool are [`ScopedSymbol`]s, which borrow the pool,
+/// so they cannot outlive it or survive a call to `clear()`:
+///
+/// ```rust,compile_fail
+/// # use stringleton_registry::ScopedPool;
+/// let symbol = {
+///     let pool = ScopedPool::new();
+///     pool.intern("level_1")
+/// };
+/// ```
+///
+/// Scoped symbols are not interchangeable with global
+/// [`Symbol`](crate::Symbol)s, and cannot be converted to them, because a
+/// global symbol must live forever. Each pool is completely independent of the
+/// global registry and of other pools.
+#[derive(Default)]
+pub struct ScopedPool {
+    // Note: The boxes are never removed while `&self` is borrowed, so their
+    // contents have stable addresses for the lifetime of that borrow.
+    strings: RefCell<HashSet<Box<str>>>,
+}
+
+/// Symbol belonging to a [`ScopedPool`].
+///
+/// Like [`Symbol`](crate::Symbol), comparison and hashing use the pointer
+/// value, which is unique per string within the same pool. Comparing symbols
+/// from different pools is meaningless.
+#[derive(Clone, Copy)]
+pub struct ScopedSymbol<'p>(&'p str);
+
+/// Local string interner that can be cleared, for fuzzing.
+///
+/// Interning fuzzer input through the global [`Registry`](crate::Registry)
+/// leaks unboundedly, and the state of the registry depends on all previous
+/// fuzz cases. A [`ScopedPool`] can be reset between fuzz iterations with
+/// [`clear()`](ScopedPool::clear), so each iteration starts from an empty
+/// pool.
+pub type FuzzRegistry = ScopedPool;
+
+/// Symbol belonging to a [`FuzzRegistry`].
+pub type FuzzSymbol<'r> = ScopedSymbol<'r>;
+
+impl ScopedPool {
+    /// Create an empty pool.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the existing symbol for `string`, or insert a new one.
+    pub fn intern(&self, string: &str) -> ScopedSymbol<'_> {
+        let mut strings = self.strings.borrow_mut();
+        let interned: &str = strings.get_or_insert_with(string, |string| Box::from(string));
+        unsafe {
+            // SAFETY: The box is only dropped by `clear()` or `drop()`, which
+            // require exclusive access to `self`.
+            ScopedSymbol(&*core::ptr::from_ref(interned))
+        }
+    }
+
+    /// Get the symbol for `string`, if it has been interned.
+    #[must_use]
+    pub fn get(&self, string: &str) -> Option<ScopedSymbol<'_>> {
+        let strings = self.strings.borrow();
+        strings.get(string).map(|interned| unsafe {
+            // SAFETY: See `intern()`.
+            ScopedSymbol(&*core::ptr::from_ref::<str>(interned))
+        })
+    }
+
+    /// Get the number of interned symbols.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.borrow().len()
+    }
+
+    /// Whether or not any symbols have been interned.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.strings.borrow().is_empty()
+    }
+
+    /// Remove the symbols for which `keep` returns `false`, freeing their
+    /// strings.
+    ///
+    /// Unlike the global registry, which can never free anything, a pool can
+    /// be pruned, for example to drop the names that are no longer referenced
+    /// after unloading part of a level. This requires exclusive access to the
+    /// pool, so no [`ScopedSymbol`]s can be alive while it runs, and the
+    /// symbols that are kept are still interned afterwards. Symbols for the
+    /// removed strings can be created again later, but they will not compare
+    /// equal to any symbol from before the call (which cannot exist anymore).
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.strings.get_mut().retain(|string| keep(string));
+    }
+
+    /// Remove all symbols, freeing their strings.
+    ///
+    /// The allocated capacity of the pool is retained, so clearing it to reuse
+    /// it (for example between fuzz iterations) is cheap.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.strings.get_mut().clear();
+    }
+}
+
+impl<'p> ScopedSymbol<'p> {
+    /// Get the string representation of this symbol.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &'p str {
+        self.0
+    }
+}
+
+impl PartialEq for ScopedSymbol<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        core::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for ScopedSymbol<'_> {}
+
+impl Hash for ScopedSymbol<'_> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.as_ptr().hash(state);
+    }
+}
+
+impl PartialEq<str> for ScopedSymbol<'_> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ScopedSymbol<'_> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Note: This impl forwards string formatting options to the underlying string.
+impl core::fmt::Display for ScopedSymbol<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self.0, f)
+    }
+}
+
+/// Note: This impl forwards string formatting options to the underlying string.
+impl core::fmt::Debug for ScopedSymbol<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.0, f)
+    }
+}
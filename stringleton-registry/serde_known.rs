@@ -0,0 +1,72 @@
+//! Deserializing symbols from a closed set of known symbols (`serde` feature).
+//!
+//! ```rust,ignore
+//! use stringleton::{StaticSymbol, Symbol, serde_known, static_sym};
+//!
+//! const MODES: [StaticSymbol; 2] = [static_sym!(fast), static_sym!(safe)];
+//!
+//! fn deserialize_mode<'de, D: serde::Deserializer<'de>>(
+//!     deserializer: D,
+//! ) -> Result<Symbol, D::Error> {
+//!     serde_known::deserialize_in(deserializer, &MODES)
+//! }
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Config {
+//!     #[serde(deserialize_with = "deserialize_mode")]
+//!     mode: Symbol,
+//! }
+//! ```
+
+use core::fmt;
+
+use serde::de;
+
+use crate::{StaticSymbol, Symbol};
+
+/// Deserialize a string that must be one of the `known` symbols, and return
+/// that symbol.
+///
+/// This never creates new symbols, so it is safe to use with untrusted input.
+/// The incoming string is compared to the strings of the known symbols one by
+/// one, so `known` is expected to be small.
+///
+/// Since `#[serde(deserialize_with = ...)]` requires a function that only
+/// takes the deserializer, wrap this in a function that passes the set of
+/// known symbols (see the [module documentation](self)).
+///
+/// # Errors
+///
+/// Returns an "invalid value" error listing the known symbols if the string is
+/// not one of them, or any error from the deserializer.
+pub fn deserialize_in<'de, D: de::Deserializer<'de>>(
+    deserializer: D,
+    known: &[StaticSymbol],
+) -> Result<Symbol, D::Error> {
+    deserializer.deserialize_str(KnownVisitor(known))
+}
+
+struct KnownVisitor<'a>(&'a [StaticSymbol]);
+
+impl de::Visitor<'_> for KnownVisitor<'_> {
+    type Value = Symbol;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("one of ")?;
+        for (i, symbol) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{symbol}`")?;
+        }
+        Ok(())
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Symbol, E> {
+        self.0
+            .iter()
+            .find(|symbol| symbol.as_str() == v)
+            .map(|symbol| **symbol)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+}
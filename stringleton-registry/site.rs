@@ -21,8 +21,16 @@ pub struct Site {
     ///   threads), so access is trivially synchronized.
     /// - After static initializers, this field is only ever read immutably.
     inner: UnsafeCell<&'static &'static str>,
-    #[cfg(any(miri, target_arch = "wasm32", feature = "debug-assertions"))]
+    #[cfg(any(
+        miri,
+        target_arch = "wasm32",
+        feature = "debug-assertions",
+        feature = "test-util"
+    ))]
     initialized: AtomicBool,
+    /// The `file!()` and `line!()` of the call site, if known.
+    #[cfg(feature = "track-caller-sites")]
+    location: Option<(&'static str, u32)>,
 }
 
 // SAFETY: The contents of `SymbolRegistration` are synchronized by (a) static
@@ -39,11 +47,49 @@ impl Site {
     pub const fn new(string: &'static &'static str) -> Self {
         Self {
             inner: UnsafeCell::new(string),
-            #[cfg(any(miri, target_arch = "wasm32", feature = "debug-assertions"))]
+            #[cfg(any(
+                miri,
+                target_arch = "wasm32",
+                feature = "debug-assertions",
+                feature = "test-util"
+            ))]
             initialized: AtomicBool::new(false),
+            #[cfg(feature = "track-caller-sites")]
+            location: None,
         }
     }
 
+    /// Create a site that remembers its source location with the
+    /// `track-caller-sites` feature. Without the feature, `file` and `line`
+    /// are ignored, and this is the same as [`Site::new()`].
+    #[inline(always)]
+    #[must_use]
+    #[doc(hidden)]
+    #[allow(unused_variables)]
+    pub const fn new_at(string: &'static &'static str, file: &'static str, line: u32) -> Self {
+        Self {
+            inner: UnsafeCell::new(string),
+            #[cfg(any(
+                miri,
+                target_arch = "wasm32",
+                feature = "debug-assertions",
+                feature = "test-util"
+            ))]
+            initialized: AtomicBool::new(false),
+            #[cfg(feature = "track-caller-sites")]
+            location: Some((file, line)),
+        }
+    }
+
+    /// The `file!()` and `line!()` of the `sym!()` call that declared this
+    /// site, or `None` if the site was not created by a macro.
+    #[cfg(feature = "track-caller-sites")]
+    #[inline]
+    #[must_use]
+    pub fn location(&self) -> Option<(&'static str, u32)> {
+        self.location
+    }
+
     /// # Safety
     ///
     /// This must only be called from the registry's static ctor, or after
@@ -64,7 +110,12 @@ impl Site {
     #[doc(hidden)]
     #[inline(always)]
     pub unsafe fn initialize(&self, interned: Symbol) {
-        #[cfg(any(miri, target_arch = "wasm32", feature = "debug-assertions"))]
+        #[cfg(any(
+            miri,
+            target_arch = "wasm32",
+            feature = "debug-assertions",
+            feature = "test-util"
+        ))]
         {
             self.initialized
                 .store(true, core::sync::atomic::Ordering::SeqCst);
@@ -74,6 +125,45 @@ impl Site {
         }
     }
 
+    /// Atomic counterpart of [`get_string()`](Self::get_string), for
+    /// [`Registry::register_sites_safe()`](crate::Registry::register_sites_safe).
+    pub(crate) fn load_string(&self) -> &'static &'static str {
+        let inner: *mut *mut &'static str = self.inner.get().cast();
+        unsafe {
+            // SAFETY: The pointer is valid and aligned for atomic access. The
+            // site is only accessed non-atomically by static ctors (which do
+            // not run concurrently with other code), and by readers that
+            // have observed the initialized value, which never changes again
+            // (see `store_interned()`).
+            &*AtomicPtr::from_ptr(inner).load(Ordering::Acquire)
+        }
+    }
+
+    /// Atomic counterpart of [`initialize()`](Self::initialize), which only
+    /// writes to the site if it does not already contain `interned`.
+    pub(crate) fn store_interned(&self, interned: Symbol) {
+        let inner: *mut *mut &'static str = self.inner.get().cast();
+        let atomic_inner = unsafe {
+            // SAFETY: See `load_string()`.
+            AtomicPtr::from_ptr(inner)
+        };
+        let ptr = core::ptr::from_ref(interned.inner()).cast_mut();
+        // Registering a table again does not write to initialized sites, so
+        // it cannot race with readers of those sites.
+        if atomic_inner.load(Ordering::Acquire) != ptr {
+            atomic_inner.store(ptr, Ordering::Release);
+        }
+        #[cfg(any(
+            miri,
+            target_arch = "wasm32",
+            feature = "debug-assertions",
+            feature = "test-util"
+        ))]
+        {
+            self.initialized.store(true, Ordering::SeqCst);
+        }
+    }
+
     /// Get a reference to the symbol contained in this site.
     ///
     /// # Safety
@@ -111,6 +201,30 @@ impl Site {
     pub unsafe fn get_after_ctor(&'static self) -> Symbol {
         unsafe { *self.get_ref_after_ctor() }
     }
+
+    /// Get a reference to the symbol contained in this site through the
+    /// fallback path that is used on targets without static constructors
+    /// (Miri and `wasm32`), where each access checks an atomic flag, and the
+    /// first access interns the symbol.
+    ///
+    /// This exists to measure the cost of the fallback path on other targets,
+    /// for example in benchmarks. It is only available with the `test-util`
+    /// feature, which adds the flag to every site.
+    ///
+    /// # Safety
+    ///
+    /// Until the site has been initialized, it must only be accessed through
+    /// this function. Sites that are registered by a static constructor, like
+    /// those of `sym!()`, are initialized after static constructors have run.
+    #[cfg(feature = "test-util")]
+    #[inline]
+    #[must_use]
+    pub unsafe fn get_ref_without_ctor(&'static self) -> &'static Symbol {
+        unsafe {
+            // SAFETY: Precondition.
+            get_without_ctor_support(self)
+        }
+    }
 }
 
 /// # Safety
@@ -121,19 +235,22 @@ impl Site {
 unsafe fn get_with_ctor_support(site: &'static Site) -> &'static Symbol {
     #[cfg(feature = "debug-assertions")]
     {
-        assert!(
-            site.initialized.load(core::sync::atomic::Ordering::Relaxed),
-            "This `sym!()` call site has not been initialized by a static constructor. This can happen for the following reasons: \n
-  a) The current platform does not support static constructors (e.g., Miri)\n
-  b) The current crate is a dynamic library, but it reuses the registration from another crate, i.e., stringleton!(foreign_crate) is being used across a dynamic linking boundary\n
-  c) The call site is somehow reached without its containing binary having its static ctor functions called"
-            );
+        // ORDERING: Synchronizes with the store in `initialize_atomic()` when
+        // another thread initialized the site late, so the non-atomic read
+        // below observes the interned pointer.
+        if !site.initialized.load(Ordering::Acquire) {
+            unsafe {
+                // SAFETY: The site is not initialized.
+                initialize_late(site);
+            }
+        }
     }
 
     unsafe {
         // SAFETY: The safety invariant is that this is only called after ctors
-        //         have run, and only ctors write to this location, so we can do
-        //         a non-atomic load.
+        //         have run, and only ctors write to this location (or
+        //         `initialize_late()`, which is synchronized above), so we can
+        //         do a non-atomic load.
         let ptr: *const &'static &'static str = site.inner.get();
         // SAFETY: Symbol is `#[repr(transparent)]`, so it is safe to cast
         //         `&&'static &'static str` to `&Symbol`.
@@ -146,7 +263,7 @@ unsafe fn get_with_ctor_support(site: &'static Site) -> &'static Symbol {
 /// `ctor` are not supported there. It performs an atomic check on every access,
 /// and is therefore a lot slower.
 #[inline(always)]
-#[cfg(any(miri, target_arch = "wasm32"))]
+#[cfg(any(miri, target_arch = "wasm32", feature = "test-util"))]
 unsafe fn get_without_ctor_support(site: &'static Site) -> &'static Symbol {
     // CAUTION:
     //
@@ -161,7 +278,15 @@ unsafe fn get_without_ctor_support(site: &'static Site) -> &'static Symbol {
         ptr.cast()
     };
 
-    if site.initialized.load(Ordering::SeqCst) {
+    // ORDERING: `initialized` only ever transitions from false to true, and
+    // the interned pointer is stored before it (with a stronger ordering), and
+    // never changes again. So a relaxed load is enough to decide which path to
+    // take, and the acquire fence on the fast path synchronizes with the store
+    // in `initialize_atomic()`, making the non-atomic read of `inner` below
+    // observe the interned pointer. This avoids a `SeqCst` load on every
+    // access, which is expensive under Miri.
+    if site.initialized.load(Ordering::Relaxed) {
+        core::sync::atomic::fence(Ordering::Acquire);
         unsafe {
             // SAFETY:
             // - Already initialized, so it is safe to access `inner`
@@ -183,7 +308,42 @@ unsafe fn get_without_ctor_support(site: &'static Site) -> &'static Symbol {
     }
 }
 
-#[cfg(any(miri, target_arch = "wasm32"))]
+/// Called when a site is reached that was not initialized by a static ctor.
+///
+/// Panics, unless an uninitialized site handler has been installed, in which
+/// case the site is initialized through the registry like on the slow path.
+///
+/// # Safety
+///
+/// Must only be called with the debug assertion, which guarantees that `site`
+/// is only accessed atomically until it is initialized.
+#[cfg(feature = "debug-assertions")]
+#[cold]
+#[inline(never)]
+unsafe fn initialize_late(site: &'static Site) {
+    let Some(handler) = crate::Registry::global().uninitialized_site_handler() else {
+        panic!(
+            "This `sym!()` call site has not been initialized by a static constructor. This can happen for the following reasons: \n
+  a) The current platform does not support static constructors (e.g., Miri)\n
+  b) The current crate is a dynamic library, but it reuses the registration from another crate, i.e., stringleton!(foreign_crate) is being used across a dynamic linking boundary\n
+  c) The call site is somehow reached without its containing binary having its static ctor functions called"
+        );
+    };
+    handler(site);
+
+    let inner_ptr: *mut *mut &'static str = site.inner.get().cast();
+    unsafe {
+        // SAFETY: See `initialize_atomic`.
+        initialize_atomic(inner_ptr, &site.initialized);
+    }
+}
+
+#[cfg(any(
+    miri,
+    target_arch = "wasm32",
+    feature = "debug-assertions",
+    feature = "test-util"
+))]
 unsafe fn initialize_atomic(inner_ptr: *mut *mut &'static str, initialized: &'static AtomicBool) {
     // Cast to an atomic pointer
     let atomic_inner: &AtomicPtr<&'static str> = unsafe {
@@ -196,8 +356,8 @@ unsafe fn initialize_atomic(inner_ptr: *mut *mut &'static str, initialized: &'st
         // SAFETY: The pointer is valid.
         //
         // RELAXED: It doesn't matter if we read an outdated value here, because
-        // `initialized` is what controls the order of operations, and we
-        // unconditionally perform a `SeqCst` load above and one below.
+        // `initialized` is what controls the order of operations, and the
+        // value is either the original string or the (equal) interned one.
         &*(atomic_inner.load(Ordering::Relaxed))
     };
 
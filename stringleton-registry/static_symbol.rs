@@ -8,6 +8,12 @@ use crate::{Site, Symbol};
 ///
 /// **CAUTION:** Declarations with `StaticSymbol` must _not_ be used before
 /// static initializers have run, i.e. before `main()`.
+///
+/// Static symbols cannot be compared in `const` contexts, because their values
+/// are only known after static initializers have run (and comparing function
+/// pointers is not allowed in `const` evaluation). To check that a table of
+/// static symbols has no duplicates, use
+/// [`assert_unique()`](Self::assert_unique) in a test or at startup.
 #[derive(Copy, Clone)]
 pub struct StaticSymbol(
     // Note: Unfortunately, we can't use a `&'static Site` reference directly,
@@ -32,6 +38,45 @@ impl StaticSymbol {
     pub const unsafe fn new_unchecked(f: fn() -> &'static Site) -> Self {
         Self(f)
     }
+
+    /// Check that `symbols` does not contain the same symbol more than once.
+    ///
+    /// This is intended for lookup tables keyed by static symbols, where a
+    /// duplicate key is a bug. Since this cannot be checked at compile time,
+    /// call it from a test or at the start of `main()`. For tables of pairs,
+    /// collect the keys first, for example `TABLE.map(|(key, _)| key)`.
+    ///
+    /// This compares each pair of symbols, so it is intended for small tables.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a symbol appears more than once, naming the symbol and its
+    /// positions.
+    pub fn assert_unique(symbols: &[StaticSymbol]) {
+        for (i, a) in symbols.iter().enumerate() {
+            for (j, b) in symbols.iter().enumerate().skip(i + 1) {
+                assert!(a != b, "duplicate symbol {a:?} at index {i} and {j}");
+            }
+        }
+    }
+
+    /// Resolve each of `symbols` once, for example during loading.
+    ///
+    /// Resolving a static symbol reads its call site through a small
+    /// trampoline function, which is cheap, but the first access may still
+    /// page in the memory of the call site and the trampoline. Warming up the
+    /// static symbols that are used in a latency-critical loop moves this cost
+    /// out of the loop. On targets without static initializers (Miri and
+    /// `wasm32`), this also interns each symbol, which would otherwise happen
+    /// on first use.
+    ///
+    /// Like all uses of `StaticSymbol`, this must not be called before static
+    /// initializers have run.
+    pub fn warmup(symbols: &[StaticSymbol]) {
+        for symbol in symbols {
+            core::hint::black_box(**symbol);
+        }
+    }
 }
 
 impl core::ops::Deref for StaticSymbol {
@@ -54,6 +99,15 @@ impl core::borrow::Borrow<Symbol> for StaticSymbol {
     }
 }
 
+// Note: `AsRef<Symbol> for &StaticSymbol` is already covered by the blanket
+// implementation for references, but `Borrow` has no such blanket.
+impl core::borrow::Borrow<Symbol> for &StaticSymbol {
+    #[inline]
+    fn borrow(&self) -> &Symbol {
+        self
+    }
+}
+
 impl AsRef<Symbol> for StaticSymbol {
     #[inline]
     fn as_ref(&self) -> &Symbol {
@@ -96,6 +150,62 @@ impl PartialEq for StaticSymbol {
     }
 }
 
+impl PartialEq<str> for StaticSymbol {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        **self == *other
+    }
+}
+
+impl PartialEq<&str> for StaticSymbol {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        **self == **other
+    }
+}
+
+impl PartialEq<StaticSymbol> for str {
+    #[inline]
+    fn eq(&self, other: &StaticSymbol) -> bool {
+        *self == **other
+    }
+}
+
+impl PartialEq<StaticSymbol> for &str {
+    #[inline]
+    fn eq(&self, other: &StaticSymbol) -> bool {
+        **self == **other
+    }
+}
+
+impl PartialOrd<str> for StaticSymbol {
+    #[inline]
+    fn partial_cmp(&self, other: &str) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(other)
+    }
+}
+
+impl PartialOrd<&str> for StaticSymbol {
+    #[inline]
+    fn partial_cmp(&self, other: &&str) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(other)
+    }
+}
+
+impl PartialOrd<StaticSymbol> for str {
+    #[inline]
+    fn partial_cmp(&self, other: &StaticSymbol) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&**other)
+    }
+}
+
+impl PartialOrd<StaticSymbol> for &str {
+    #[inline]
+    fn partial_cmp(&self, other: &StaticSymbol) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&**other)
+    }
+}
+
 impl core::fmt::Debug for StaticSymbol {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
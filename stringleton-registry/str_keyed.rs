@@ -0,0 +1,82 @@
+use core::{borrow::Borrow, hash::Hash};
+
+use crate::Symbol;
+
+/// Symbol wrapper for hash maps that are also queried by `&str`.
+///
+/// `Symbol` does not implement `Borrow<str>`, because it hashes by pointer
+/// value (see [`Symbol`]). This wrapper instead hashes the string contents, so
+/// it can implement `Borrow<str>`, and maps keyed by `StrKeyed` can be queried
+/// with plain strings:
+///
+/// ```rust,ignore
+/// let mut map = HashMap::new();
+/// map.insert(StrKeyed(sym!(foo)), 1);
+/// assert_eq!(map.get("foo"), Some(&1));
+/// ```
+///
+/// Equality is still a pointer comparison, which is equivalent to comparing
+/// the strings. Note that hashing a `StrKeyed` hashes the whole string, so it
+/// is slower than hashing a `Symbol`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct StrKeyed(pub Symbol);
+
+impl Hash for StrKeyed {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // Must match the `Hash` impl of `str`, because of `Borrow<str>`.
+        self.0.as_str().hash(state);
+    }
+}
+
+impl Borrow<str> for StrKeyed {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl AsRef<str> for StrKeyed {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl core::ops::Deref for StrKeyed {
+    type Target = Symbol;
+
+    #[inline]
+    fn deref(&self) -> &Symbol {
+        &self.0
+    }
+}
+
+impl From<Symbol> for StrKeyed {
+    #[inline]
+    fn from(value: Symbol) -> Self {
+        Self(value)
+    }
+}
+
+impl From<StrKeyed> for Symbol {
+    #[inline]
+    fn from(value: StrKeyed) -> Self {
+        value.0
+    }
+}
+
+impl core::fmt::Debug for StrKeyed {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl core::fmt::Display for StrKeyed {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
+    }
+}
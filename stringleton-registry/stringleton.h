@@ -0,0 +1,35 @@
+/* C API for the stringleton symbol registry.
+ *
+ * Available when the `capi` feature of `stringleton` (or `stringleton-dylib`)
+ * is enabled. Symbols are passed as opaque 64-bit handles, which are only
+ * meaningful within the process that produced them. The handle value 0 is
+ * never a valid symbol. */
+
+#ifndef STRINGLETON_H
+#define STRINGLETON_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+/* Handle for a symbol, equivalent to `Symbol::to_ffi()` on the Rust side. */
+typedef uint64_t SymbolFfi;
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Get the UTF-8 string (not nul-terminated) of the symbol represented by
+ * `handle`, writing its length to `len` if `len` is not NULL. The string lives
+ * for the rest of the program. Returns NULL if `handle` is not a registered
+ * symbol. */
+const uint8_t *stringleton_symbol_as_str(SymbolFfi handle, size_t *len);
+
+/* Get the symbol for the UTF-8 string at `ptr` with length `len`, creating it
+ * if necessary. Returns 0 if the string is not valid UTF-8. */
+SymbolFfi stringleton_symbol_from_str(const uint8_t *ptr, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* STRINGLETON_H */
@@ -6,7 +6,7 @@ use std as alloc;
 #[cfg(feature = "alloc")]
 use alloc::{borrow::Cow, string::String};
 
-use crate::Registry;
+use crate::{Registry, StaticArena};
 
 /// Interned string with very fast comparison and hashing.
 ///
@@ -38,6 +38,18 @@ use crate::Registry;
 /// The hash value of symbols may change even between invocations of the same
 /// binary, so should not be relied upon in any way.
 ///
+/// `Symbol` is `Send` and `Sync` (it is a `&'static` reference to immutable
+/// data), so it can be used as the key of concurrent maps, like `DashMap`. Use
+/// [`BuildSymbolHasher`](crate::BuildSymbolHasher) as the hasher of such maps
+/// to hash symbols by pointer cheaply (see also `ConcurrentSymbolMap` with the
+/// `dashmap` feature).
+///
+/// When the `stable-hash` feature is enabled, symbols instead hash the contents
+/// of the underlying string, which makes the iteration order of symbol-keyed
+/// hash maps reproducible (given a deterministic hasher), at the cost of
+/// hashing the whole string. Note that enabling the feature changes the
+/// iteration order of all such maps in the program.
+///
 /// # Leaks
 ///
 /// Once created, symbols are never freed, and there is no way to
@@ -76,13 +88,235 @@ impl Symbol {
         Self::new_(string.as_ref())
     }
 
+    /// Create a deduplicated symbol at runtime, and report whether it is new.
+    ///
+    /// The returned `bool` is `true` if this call created the symbol, and
+    /// `false` if it already existed, which can be used to detect unexpected
+    /// symbol creation. Otherwise, this is the same as [`new()`](Self::new).
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn new_reporting(string: &str) -> (Symbol, bool) {
+        Registry::global().get_or_insert_reporting(string)
+    }
+
+    /// Create a deduplicated symbol at runtime, unless it would exceed the
+    /// limits of the registry.
+    ///
+    /// This is the same as [`new()`](Self::new), but new symbols are checked
+    /// against the limit set by [`Registry::set_max_symbol_len()`] before
+    /// anything is allocated. Use this for strings from untrusted sources.
+    ///
+    /// # Errors
+    ///
+    /// See [`Registry::try_get_or_insert()`].
+    #[inline]
+    #[cfg(feature = "alloc")]
+    pub fn try_new(string: impl AsRef<str>) -> Result<Symbol, crate::InternError> {
+        Registry::global().try_get_or_insert(string.as_ref())
+    }
+
+    /// Get the symbol for a borrowed string, if it has already been
+    /// registered.
+    ///
+    /// This is the same as [`get()`](Self::get), but returns a `Result`, and
+    /// is the fallible counterpart of [`new()`](Self::new) that is also
+    /// available without the `alloc` feature. Without `alloc`, symbols can
+    /// only be created from `'static` strings (through `sym!()` or
+    /// [`new_static()`](Self::new_static)), but they can be looked up by any
+    /// string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotInterned`](crate::NotInterned) if `string` has not been
+    /// registered as a symbol. Nothing is inserted in that case.
+    #[inline]
+    pub fn try_new_borrowed(string: &str) -> Result<Symbol, crate::NotInterned> {
+        Self::get_(string).ok_or(crate::NotInterned)
+    }
+
     #[inline]
     #[must_use]
     #[cfg(feature = "alloc")]
     fn new_(string: &str) -> Symbol {
+        #[cfg(feature = "thread-cache")]
+        return crate::thread_cache::get_or_insert(string);
+        #[cfg(not(feature = "thread-cache"))]
         Registry::global().get_or_insert(string)
     }
 
+    /// Create deduplicated symbols at runtime from a fixed number of strings.
+    ///
+    /// This is equivalent to calling [`new()`](Self::new) for each string, but
+    /// the registry's write lock is only acquired once. Useful when a handful of
+    /// dynamic symbols is needed together:
+    ///
+    /// ```rust
+    /// # use stringleton_registry::Symbol;
+    /// let [a, b, c] = Symbol::intern_all(["a", "b", "c"]);
+    /// assert_eq!([a, b, c], [Symbol::new("a"), Symbol::new("b"), Symbol::new("c")]);
+    /// ```
+    ///
+    /// For a number of strings that is not known at compile time, use
+    /// [`Registry::batch()`].
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn intern_all<const N: usize>(strings: [&str; N]) -> [Symbol; N] {
+        let mut batch = Registry::global().batch();
+        strings.map(|string| batch.intern(string))
+    }
+
+    /// Create a deduplicated symbol at runtime from an owned string.
+    ///
+    /// This is the same as [`new()`](Self::new), except that if the symbol
+    /// has not been registered before, `string` itself is leaked and used as
+    /// the storage of the symbol, instead of allocating a copy. If the symbol
+    /// already exists, `string` is dropped.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn from_boxed_str(string: alloc::boxed::Box<str>) -> Symbol {
+        Registry::global().get_or_insert_boxed(string)
+    }
+
+    /// Create a deduplicated symbol at runtime from a reusable string buffer.
+    ///
+    /// If the symbol already exists, `string` is left untouched, so the buffer
+    /// can be reused for the next symbol. Otherwise, the contents of `string`
+    /// are taken and become the storage of the new symbol (see
+    /// [`from_boxed_str()`](Self::from_boxed_str)), leaving `string` empty and
+    /// without capacity.
+    ///
+    /// This saves an allocation and a copy on each miss, compared to
+    /// [`new()`](Self::new), when filling and interning a scratch buffer in a
+    /// loop. It has the same memory leak caveats as `new()`.
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn new_take(string: &mut String) -> Symbol {
+        if let Some(existing) = Self::get(string.as_str()) {
+            return existing;
+        }
+        Self::from_boxed_str(core::mem::take(string).into_boxed_str())
+    }
+
+    /// Create a deduplicated symbol at runtime from formatting arguments.
+    ///
+    /// This formats `args` into a new string, which becomes the storage of the
+    /// symbol if it has not been registered before (see
+    /// [`from_boxed_str()`](Self::from_boxed_str)), so unlike
+    /// `Symbol::new(format!(...))`, the formatted string is not copied again.
+    /// Otherwise, this has the same overhead and memory leak caveats as
+    /// [`new()`](Self::new).
+    ///
+    /// The [`sym_fmt!(...)`](../stringleton/macro.sym_fmt.html) macro is a
+    /// shorthand for this function.
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn new_fmt(args: core::fmt::Arguments<'_>) -> Symbol {
+        match args.as_str() {
+            // No formatting necessary.
+            Some(string) => Self::new_(string),
+            None => Self::from_boxed_str(alloc::fmt::format(args).into_boxed_str()),
+        }
+    }
+
+    /// Create a deduplicated symbol at runtime from an OS string.
+    ///
+    /// Symbols are always valid UTF-8, so this returns `None` if `string` is
+    /// not valid UTF-8 (which can happen with file names on most platforms).
+    /// No lossy conversion is ever performed. Otherwise, this is equivalent to
+    /// [`new()`](Self::new), with the same overhead and memory leak caveats.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "std")]
+    pub fn new_os(string: &std::ffi::OsStr) -> Option<Symbol> {
+        string.to_str().map(Self::new_)
+    }
+
+    /// Create a deduplicated symbol at runtime from a path.
+    ///
+    /// This returns `None` if the path is not valid UTF-8. See
+    /// [`new_os()`](Self::new_os).
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "std")]
+    pub fn new_path(path: &std::path::Path) -> Option<Symbol> {
+        Self::new_os(path.as_os_str())
+    }
+
+    /// Create a deduplicated symbol at runtime from the Unicode Normalization
+    /// Form C (NFC) of `string`.
+    ///
+    /// Canonically equivalent strings, such as `"caf\u{e9}"` (composed) and
+    /// `"cafe\u{301}"` (decomposed), produce the same symbol, whose string is
+    /// the composed form. This is not case folding: `"Café"` and `"café"` are
+    /// still different symbols.
+    ///
+    /// Normalization only happens here, when interning, so comparing the
+    /// resulting symbols is still a pointer comparison. Symbols created by
+    /// other means, like [`new()`](Self::new) and `sym!()`, are not
+    /// normalized, so only mix them with normalized symbols when their strings
+    /// are known to be in NFC (like all ASCII strings).
+    ///
+    /// Strings that are already in NFC are interned without copying them
+    /// first. Otherwise, this has the same overhead and memory leak caveats as
+    /// [`new()`](Self::new).
+    #[must_use]
+    #[cfg(feature = "unicode-norm")]
+    pub fn new_normalized(string: &str) -> Symbol {
+        use unicode_normalization::{IsNormalized, UnicodeNormalization, is_nfc_quick};
+
+        if is_nfc_quick(string.chars()) == IsNormalized::Yes {
+            return Self::new_(string);
+        }
+        let mut normalized: String = string.nfc().collect();
+        Self::new_take(&mut normalized)
+    }
+
+    /// Create a child symbol in the namespace of this symbol.
+    ///
+    /// This interns the string `"{self}{sep}{name}"`. For example,
+    /// `sym!("app.ui").child("button", '.')` returns `sym!("app.ui.button")`.
+    ///
+    /// This has the same overhead and memory leak caveats as
+    /// [`new()`](Self::new).
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn child(&self, name: &str, sep: char) -> Symbol {
+        let mut string = String::with_capacity(self.len() + sep.len_utf8() + name.len());
+        string.push_str(self.as_str());
+        string.push(sep);
+        string.push_str(name);
+        Symbol::new(string)
+    }
+
+    /// Split the string of this symbol by `sep`, interning each part as a
+    /// symbol.
+    ///
+    /// For example, `sym!("system.render.shadow").split('.')` yields
+    /// `sym!(system)`, `sym!(render)`, and `sym!(shadow)`. This is the inverse
+    /// of [`child()`](Self::child).
+    ///
+    /// Each part is interned with [`new()`](Self::new), so this has the same
+    /// overhead and memory leak caveats. It is intended for keys with a bounded
+    /// set of parts, such as statically known hierarchical names, and should
+    /// not be used on arbitrary runtime data.
+    #[cfg(feature = "alloc")]
+    pub fn split(&self, sep: char) -> impl Iterator<Item = Symbol> + use<> {
+        self.as_str().split(sep).map(Self::new_)
+    }
+
+    /// Create a symbol from another interned string type.
+    ///
+    /// This is equivalent to `Symbol::new(other.as_str())`. To convert in the
+    /// other direction, use `T::from_str_interned(symbol.as_str())`.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn convert_from<T: InternedString + ?Sized>(other: &T) -> Symbol {
+        Self::new_(other.as_str())
+    }
+
     /// Create a deduplicated symbol at runtime from a static reference to a
     /// static string.
     ///
@@ -107,6 +341,69 @@ impl Symbol {
         Registry::global().get_or_insert_static(string)
     }
 
+    /// Create a deduplicated symbol at runtime from a static string.
+    ///
+    /// This is the same as [`new_static()`](Self::new_static), for strings
+    /// that are `'static`, but not behind a `'static` reference, such as the
+    /// result of [`String::leak()`]. The string is not copied, but when the
+    /// symbol is new, a reference to the string is allocated and leaked.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "alloc")]
+    pub fn new_static_str(string: &'static str) -> Symbol {
+        Registry::global().get_or_insert_static_str(string)
+    }
+
+    /// Create a deduplicated symbol at runtime, copying the string into
+    /// `arena` instead of allocating it on the heap.
+    ///
+    /// This is available without the `alloc` feature. If the symbol already
+    /// exists, it is returned without using any space in the arena. Otherwise,
+    /// the string is copied into the arena, or `None` is returned if the arena
+    /// is full.
+    ///
+    /// This takes a global read lock, and a write lock if the symbol is new.
+    #[must_use]
+    pub fn new_in_arena<const N: usize>(
+        arena: &'static StaticArena<N>,
+        string: &str,
+    ) -> Option<Symbol> {
+        let registry = Registry::global();
+        if let Some(previously_interned) = registry.get(string) {
+            return Some(previously_interned);
+        }
+        let mut write = registry.write();
+        if let Some(previously_interned) = write.get(string) {
+            return Some(previously_interned);
+        }
+        Some(write.get_or_insert_static(arena.alloc_str(string)?))
+    }
+
+    /// Create a deduplicated symbol at runtime from a static reference to a
+    /// static byte string, without checking that it is valid UTF-8.
+    ///
+    /// This is the same as [`new_static()`](Self::new_static), for parsers
+    /// that already know that their input is valid UTF-8 (for example, because
+    /// it is ASCII), and want to skip validating it again.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must be valid UTF-8. Creating a symbol from invalid UTF-8 is
+    /// undefined behavior, and the symbol is registered globally, so it can be
+    /// observed as a `&str` from anywhere in the program, forever. When in
+    /// doubt, use `core::str::from_utf8()` and `new_static()` instead.
+    #[inline]
+    #[must_use]
+    pub unsafe fn new_static_bytes_unchecked(bytes: &'static &'static [u8]) -> Symbol {
+        unsafe {
+            // SAFETY: `str` has the same layout as `[u8]`, so `&str` has the
+            // same layout as `&[u8]`, and the caller guarantees that the bytes
+            // are valid UTF-8.
+            let string = &*core::ptr::from_ref(bytes).cast::<&'static str>();
+            Self::new_static(string)
+        }
+    }
+
     /// Get a previously registered symbol.
     ///
     /// This returns `None` if the string has not previously been registered.
@@ -123,6 +420,20 @@ impl Symbol {
         Registry::global().get(string)
     }
 
+    /// Get a previously registered symbol from a static string.
+    ///
+    /// This is the same as [`get()`](Self::get), and never inserts or
+    /// allocates. It exists for code that only deals in the same
+    /// `&'static &'static str` values as [`new_static()`](Self::new_static).
+    ///
+    /// Note that the returned symbol is not necessarily backed by `string`,
+    /// if an equal string was registered first.
+    #[inline]
+    #[must_use]
+    pub fn get_static(string: &'static &'static str) -> Option<Symbol> {
+        Self::get_(string)
+    }
+
     /// New pre-interned symbol
     ///
     /// # Safety
@@ -167,11 +478,179 @@ impl Symbol {
         unsafe { NonNull::new_unchecked(core::ptr::from_ref::<&'static str>(self.0) as *mut _) }
     }
 
+    /// Get the underlying pointer value of this symbol as an integer.
+    ///
+    /// This is the value that symbols hash by (unless the `stable-hash`
+    /// feature is enabled).
+    #[inline]
+    #[must_use]
+    pub fn as_ptr_usize(&self) -> usize {
+        self.as_ptr().as_ptr().addr()
+    }
+
+    /// Get the address of the string data of this symbol.
+    ///
+    /// This is the same as `self.as_str().as_ptr()`, which is different from
+    /// [`as_ptr()`](Self::as_ptr) and [`to_ffi()`](Self::to_ffi): a symbol is
+    /// a pointer to a `&'static str`, which in turn points to the string data.
+    /// Symbol identity is the address of the reference, not of the data.
+    ///
+    /// This is a debugging aid for dynamic linking problems. Two symbols from
+    /// different registries have different [`as_ptr()`](Self::as_ptr), but may
+    /// still share their string data, for example when both were created from
+    /// the same string literal with [`new_static_str()`](Self::new_static_str).
+    #[inline]
+    #[must_use]
+    pub const fn str_data_ptr(&self) -> *const u8 {
+        self.as_str().as_ptr()
+    }
+
+    /// Get the ID of this symbol.
+    ///
+    /// IDs are dense integers, assigned to symbols in the order they are
+    /// registered, starting at 0. The ID of a symbol never changes within a
+    /// process, except through [`Registry::import_table()`], which panics once
+    /// any ID has been observed. IDs are not stable between runs, because
+    /// registration order depends on link order and program behavior, unless
+    /// the same table is imported.
+    ///
+    /// This takes a global read lock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the symbol was not created through the global registry, which
+    /// is only possible by misusing [`new_unchecked()`](Self::new_unchecked).
+    #[inline]
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        Registry::global()
+            .read()
+            .id_of(*self)
+            .expect("symbol is not registered")
+    }
+
+    /// Get the [IDs](Self::id) of several symbols, taking the global read lock
+    /// only once.
+    ///
+    /// This is useful for caching the IDs of a fixed set of symbols, for
+    /// example for membership tests with
+    /// [`SymbolBitSet::contains_id()`](crate::SymbolBitSet::contains_id).
+    ///
+    /// # Panics
+    ///
+    /// See [`id()`](Self::id).
+    #[must_use]
+    pub fn ids_of<const N: usize>(symbols: [Symbol; N]) -> [u32; N] {
+        let read = Registry::global().read();
+        symbols.map(|symbol| read.id_of(symbol).expect("symbol is not registered"))
+    }
+
+    /// Compare two symbols by their [IDs](Self::id), i.e., in the order they
+    /// were registered.
+    ///
+    /// This is a total order that is consistent within a process, and cheaper
+    /// than comparing the strings of long symbols, so it can be used to sort
+    /// symbols deterministically during a single run. It is not a lexical
+    /// order (the `Ord` implementation is not either, because it compares
+    /// addresses; use [`ByStr`](crate::ByStr) or `Symbol::sort_lexically()`
+    /// for that), and it is not stable between runs, for the same reasons as
+    /// the IDs themselves.
+    ///
+    /// This takes a global read lock, unless the symbols are equal. When
+    /// sorting many symbols, prefer sorting by a cached [`id()`](Self::id).
+    ///
+    /// # Panics
+    ///
+    /// See [`id()`](Self::id).
+    #[must_use]
+    pub fn cmp_by_id(&self, other: &Symbol) -> core::cmp::Ordering {
+        if self == other {
+            return core::cmp::Ordering::Equal;
+        }
+        let read = Registry::global().read();
+        let id = |symbol| read.id_of(symbol).expect("symbol is not registered");
+        id(*self).cmp(&id(*other))
+    }
+
+    /// Get the symbol with the ID `id`, if any. See [`id()`](Self::id).
+    ///
+    /// This takes a global read lock.
+    #[inline]
+    #[must_use]
+    pub fn from_id(id: u32) -> Option<Symbol> {
+        Registry::global().read().get_by_id(id)
+    }
+
     /// Convert the symbol to an FFI-friendly `u64`.
     #[inline]
     #[must_use]
     pub fn to_ffi(&self) -> u64 {
-        self.as_ptr().as_ptr() as usize as u64
+        self.as_ptr_usize() as u64
+    }
+
+    /// Format the symbol together with its FFI handle, for logging.
+    ///
+    /// The output has the form `sym#<hex>("string")`, where `<hex>` is the
+    /// value of [`to_ffi()`](Symbol::to_ffi) in hexadecimal. Logging this on
+    /// both sides of an FFI boundary makes it possible to correlate handles
+    /// with their strings.
+    #[inline]
+    #[must_use]
+    pub fn ffi_debug(&self) -> impl core::fmt::Display + use<> {
+        crate::ffi::FfiDebug(*self)
+    }
+
+    /// Assert that `left` and `right` are the same symbol.
+    ///
+    /// This is the same check as `assert_eq!(left, right)`, but the panic
+    /// message includes the FFI handles of both symbols (see
+    /// [`ffi_debug()`](Symbol::ffi_debug)), and points out when the strings
+    /// are equal. Equal strings with different handles mean that the symbols
+    /// come from different registries, which usually happens when a dynamic
+    /// library links its own copy of the registry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `left` and `right` are not the same symbol.
+    #[cfg(feature = "test-util")]
+    #[track_caller]
+    pub fn assert_same(left: Symbol, right: Symbol) {
+        if left == right {
+            return;
+        }
+        let hint = if left.as_str() == right.as_str() {
+            " (equal strings from different registries)"
+        } else {
+            ""
+        };
+        panic!(
+            "symbols are not the same{hint}\n  left: {}\n right: {}",
+            left.ffi_debug(),
+            right.ffi_debug()
+        );
+    }
+
+    /// Format the symbol Lisp-style, with a leading colon.
+    ///
+    /// Symbols consisting only of alphanumeric characters and any of
+    /// `-_+*/<>=!?.$%&~^` are printed as-is after the colon. Any other symbol
+    /// (including the empty symbol) is quoted and escaped like the [`Debug`]
+    /// implementation of `str`.
+    ///
+    /// The default [`Display`] and [`Debug`] implementations are unaffected.
+    ///
+    /// ```rust
+    /// # use stringleton_registry::Symbol;
+    /// assert_eq!(Symbol::new("foo").display_lisp().to_string(), ":foo");
+    /// assert_eq!(Symbol::new("foo bar").display_lisp().to_string(), r#":"foo bar""#);
+    /// ```
+    ///
+    /// [`Debug`]: core::fmt::Debug
+    /// [`Display`]: core::fmt::Display
+    #[inline]
+    #[must_use]
+    pub fn display_lisp(&self) -> impl core::fmt::Display + use<> {
+        DisplayLisp(*self)
     }
 
     /// Reconstitute a symbol from a value previously produced by
@@ -202,6 +681,215 @@ impl Symbol {
         Registry::global().get_by_address(value)
     }
 
+    /// Convert the symbol to an FFI handle, tagged with
+    /// [`Registry::abi_version()`].
+    ///
+    /// Use this instead of [`to_ffi()`](Symbol::to_ffi) when handles cross a
+    /// boundary between binaries that may have been built against different
+    /// versions of this crate, like dynamically loaded plugins.
+    #[inline]
+    #[must_use]
+    pub fn to_ffi_tagged(&self) -> (u32, u64) {
+        (Registry::abi_version(), self.to_ffi())
+    }
+
+    /// Reconstitute a symbol from a tagged handle produced by
+    /// [`to_ffi_tagged()`](Symbol::to_ffi_tagged), checking that it is valid.
+    ///
+    /// Returns `None` without looking at the address if the tag is not the
+    /// [`Registry::abi_version()`] of this binary. Otherwise, this is the same
+    /// as [`try_from_ffi()`](Symbol::try_from_ffi).
+    #[inline]
+    #[must_use]
+    pub fn try_from_ffi_tagged((version, value): (u32, u64)) -> Option<Symbol> {
+        if version != Registry::abi_version() {
+            return None;
+        }
+        Self::try_from_ffi(value)
+    }
+
+    /// Check that this symbol is registered in the global registry of the
+    /// current process.
+    ///
+    /// Symbols created through this crate are always valid, so this is only
+    /// meaningful for symbols of uncertain provenance, such as symbols handed
+    /// over by a dynamically loaded plugin that may have been built against a
+    /// different copy of `stringleton-registry`. Such symbols must not be
+    /// trusted (e.g. compared to other symbols) unless this returns `true`.
+    ///
+    /// This takes a global read lock.
+    #[inline]
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        Registry::global().get_by_address(self.to_ffi()).is_some()
+    }
+
+    /// Check if the string of this symbol is equal to `other`, ignoring ASCII
+    /// case.
+    ///
+    /// This is a plain string comparison, which does not create any symbols.
+    #[inline]
+    #[must_use]
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.as_str().eq_ignore_ascii_case(other)
+    }
+
+    /// Return the rest of the symbol's string after `prefix`, or `None` if it
+    /// does not start with `prefix`.
+    ///
+    /// The result borrows from the interned string, so no new symbol is
+    /// created.
+    #[inline]
+    #[must_use]
+    pub fn strip_prefix(&self, prefix: &str) -> Option<&'static str> {
+        self.as_str().strip_prefix(prefix)
+    }
+
+    /// Return the symbol's string before `suffix`, or `None` if it does not
+    /// end with `suffix`.
+    ///
+    /// The result borrows from the interned string, so no new symbol is
+    /// created.
+    #[inline]
+    #[must_use]
+    pub fn strip_suffix(&self, suffix: &str) -> Option<&'static str> {
+        self.as_str().strip_suffix(suffix)
+    }
+
+    /// The longest common prefix of the strings of `symbols`, e.g. for
+    /// auto-completion.
+    ///
+    /// The result borrows from the string of the first symbol, so no new symbol
+    /// is created. Returns `""` if `symbols` is empty or the strings have no
+    /// common prefix. The prefix always ends on a `char` boundary.
+    #[must_use]
+    pub fn common_prefix(symbols: &[Symbol]) -> &'static str {
+        let Some((first, rest)) = symbols.split_first() else {
+            return "";
+        };
+        let first = first.as_str();
+        let mut len = first.len();
+        for symbol in rest {
+            len = first[..len]
+                .char_indices()
+                .zip(symbol.as_str().chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(len.min(symbol.len()), |((i, _), _)| i);
+            if len == 0 {
+                break;
+            }
+        }
+        &first[..len]
+    }
+
+    /// Split the symbol's string into words, for example to build a
+    /// word-based search index over symbol names.
+    ///
+    /// Words are separated by `_`, `-`, and `.`, and by case transitions in
+    /// camel case: a word ends before an uppercase letter that follows a
+    /// lowercase letter or a digit, and before the last uppercase letter of an
+    /// acronym that is followed by a lowercase letter. Separators are not part
+    /// of any word, and empty words are skipped.
+    ///
+    /// For example, `"renderShadowMap"` yields `"render"`, `"Shadow"`, and
+    /// `"Map"`, and `"parse_HTTPResponse"` yields `"parse"`, `"HTTP"`, and
+    /// `"Response"`.
+    ///
+    /// The words borrow from the interned string, so no new symbols are
+    /// created.
+    pub fn words(&self) -> impl Iterator<Item = &'static str> + use<> {
+        self.as_str()
+            .split(['_', '-', '.'])
+            .filter(|part| !part.is_empty())
+            .flat_map(|part| {
+                let mut rest = part;
+                core::iter::from_fn(move || {
+                    if rest.is_empty() {
+                        return None;
+                    }
+                    let (word, tail) = rest.split_at(camel_word_len(rest));
+                    rest = tail;
+                    Some(word)
+                })
+            })
+    }
+
+    /// Compute the hash of this symbol with `state`.
+    ///
+    /// This is guaranteed to produce the same value as hashing the symbol
+    /// through its `Hash` implementation, so it can be used with APIs that take
+    /// a precomputed hash, like `hashbrown::HashTable`.
+    #[inline]
+    #[must_use]
+    pub fn hash_one<S: core::hash::BuildHasher>(&self, state: &S) -> u64 {
+        state.hash_one(self)
+    }
+
+    /// Compute a content hash of the symbol's string, which is the same in all
+    /// processes and on all platforms.
+    ///
+    /// Unlike the `Hash` implementation, which uses the pointer value (unless
+    /// the `stable-hash` feature is enabled), this can be persisted, for
+    /// example as part of a cache key written to disk.
+    ///
+    /// The algorithm is 64-bit FNV-1a over the UTF-8 bytes of the string. It
+    /// will not change without a major version bump of this crate.
+    ///
+    /// FNV-1a is fast for short strings, but it is not a cryptographic hash,
+    /// and collisions can be constructed deliberately, so do not use it for
+    /// strings from untrusted sources. For unrelated strings, the chance of
+    /// any collision is roughly `n² / 2⁶⁵` for `n` distinct strings, so
+    /// consider storing the string alongside the fingerprint if a collision
+    /// would be harmful.
+    #[inline]
+    #[must_use]
+    pub fn fingerprint(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+        self.as_str().bytes().fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+    }
+
+    /// Look up this symbol in a [`phf::Map`] keyed by strings.
+    ///
+    /// This is the same as `map.get(symbol.as_str())`, for existing `phf` maps:
+    ///
+    /// ```rust
+    /// # use stringleton_registry::Symbol;
+    /// static EXIT_CODES: phf::Map<&'static str, i32> = phf::phf_map! {
+    ///     "ok" => 0,
+    ///     "usage" => 64,
+    /// };
+    ///
+    /// assert_eq!(Symbol::new("usage").phf_get(&EXIT_CODES), Some(&64));
+    /// assert_eq!(Symbol::new("unknown").phf_get(&EXIT_CODES), None);
+    /// ```
+    ///
+    /// To generate the map from symbols instead, see the `perfect_symbol_set!`
+    /// macro in `stringleton`.
+    #[inline]
+    #[must_use]
+    #[cfg(feature = "phf")]
+    pub fn phf_get<'a, V>(&self, map: &'a phf::Map<&'static str, V>) -> Option<&'a V> {
+        map.get(self.as_str())
+    }
+
+    /// Write the string of this symbol to `w`.
+    ///
+    /// This is equivalent to `write!(w, "{symbol}")`, but skips the formatting
+    /// machinery. Like all inspection methods of `Symbol`, this does not need
+    /// the `alloc` feature, so it can be used to write symbols to fixed-size
+    /// buffers in `no_std` environments.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `w` fails, e.g. because a buffer is full.
+    #[inline]
+    pub fn write_to<W: core::fmt::Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        w.write_str(self.as_str())
+    }
+
     /// Length of the underlying string.
     #[inline]
     #[must_use]
@@ -254,6 +942,83 @@ impl PartialEq<Symbol> for &str {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl PartialEq<String> for Symbol {
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        *self.as_str() == **other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PartialEq<Symbol> for String {
+    #[inline]
+    fn eq(&self, other: &Symbol) -> bool {
+        **self == *other.as_str()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PartialEq<Cow<'_, str>> for Symbol {
+    #[inline]
+    fn eq(&self, other: &Cow<'_, str>) -> bool {
+        *self.as_str() == **other
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PartialEq<Symbol> for Cow<'_, str> {
+    #[inline]
+    fn eq(&self, other: &Symbol) -> bool {
+        **self == *other.as_str()
+    }
+}
+
+/// Extension methods for `Option<Symbol>`.
+///
+/// `PartialEq<str>` cannot be implemented for `Option<Symbol>`, because neither
+/// type is local to this crate. This trait fills the gap.
+pub trait OptionSymbolExt {
+    /// Compare the string of the contained symbol with `other`.
+    ///
+    /// Returns `false` if `self` is `None`. Note that `None` is never equal to
+    /// anything, including the empty string.
+    fn eq_str(&self, other: &str) -> bool;
+}
+
+impl OptionSymbolExt for Option<Symbol> {
+    #[inline]
+    fn eq_str(&self, other: &str) -> bool {
+        self.is_some_and(|symbol| symbol == *other)
+    }
+}
+
+/// Common interface for interned string types.
+///
+/// This can be implemented for the interned string types of other libraries to
+/// convert between them and [`Symbol`] generically, through
+/// [`Symbol::convert_from()`] and [`InternedString::from_str_interned()`].
+pub trait InternedString {
+    /// Get the string contents.
+    fn as_str(&self) -> &str;
+
+    /// Intern `string`.
+    fn from_str_interned(string: &str) -> Self;
+}
+
+#[cfg(feature = "alloc")]
+impl InternedString for Symbol {
+    #[inline]
+    fn as_str(&self) -> &str {
+        Symbol::as_str(self)
+    }
+
+    #[inline]
+    fn from_str_interned(string: &str) -> Self {
+        Symbol::new(string)
+    }
+}
+
 impl PartialOrd for Symbol {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
@@ -297,9 +1062,15 @@ impl PartialOrd<Symbol> for &str {
 }
 
 impl Hash for Symbol {
+    #[cfg(not(feature = "stable-hash"))]
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.as_ptr().hash(state);
     }
+
+    #[cfg(feature = "stable-hash")]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
 }
 
 impl AsRef<str> for Symbol {
@@ -309,6 +1080,15 @@ impl AsRef<str> for Symbol {
     }
 }
 
+/// Interned strings live forever, so a symbol can be converted to a
+/// `&'static str`.
+impl From<Symbol> for &'static str {
+    #[inline]
+    fn from(value: Symbol) -> Self {
+        value.as_str()
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl From<&str> for Symbol {
     #[inline]
@@ -332,6 +1112,43 @@ impl<'a> From<Cow<'a, str>> for Symbol {
     }
 }
 
+/// Length of the first camel case word of `string`. See [`Symbol::words()`].
+fn camel_word_len(string: &str) -> usize {
+    let mut chars = string.char_indices().peekable();
+    let Some((_, mut prev)) = chars.next() else {
+        return 0;
+    };
+    while let Some((index, c)) = chars.next() {
+        if c.is_uppercase() {
+            if prev.is_lowercase() || prev.is_numeric() {
+                return index;
+            }
+            if prev.is_uppercase() && chars.peek().is_some_and(|&(_, next)| next.is_lowercase()) {
+                return index;
+            }
+        }
+        prev = c;
+    }
+    string.len()
+}
+
+struct DisplayLisp(Symbol);
+
+impl core::fmt::Display for DisplayLisp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let string = self.0.as_str();
+        let is_bare = !string.is_empty()
+            && string
+                .chars()
+                .all(|c| c.is_alphanumeric() || "-_+*/<>=!?.$%&~^".contains(c));
+        if is_bare {
+            write!(f, ":{string}")
+        } else {
+            write!(f, ":{string:?}")
+        }
+    }
+}
+
 /// Note: This impl forwards string formatting options to the underlying string.
 impl core::fmt::Display for Symbol {
     #[inline]
@@ -359,25 +1176,86 @@ const _: () = {
         }
     }
 
-    #[cfg(feature = "alloc")]
+    /// Deserializes from any string, including map keys. Without the `alloc`
+    /// feature, only strings that are already interned can be deserialized.
     impl<'de> serde::Deserialize<'de> for Symbol {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
             D: serde::Deserializer<'de>,
         {
-            let s = Cow::<'de, str>::deserialize(deserializer)?;
-            Ok(Symbol::new(&*s))
+            deserializer.deserialize_str(SymbolVisitor)
         }
     }
 
-    #[cfg(not(feature = "alloc"))]
-    impl<'de> serde::Deserialize<'de> for Symbol {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: serde::Deserializer<'de>,
-        {
-            let s = <&'de str>::deserialize(deserializer)?;
-            Ok(Symbol::new(&*s))
+    struct SymbolVisitor;
+
+    impl serde::de::Visitor<'_> for SymbolVisitor {
+        type Value = Symbol;
+
+        fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            f.write_str("a string")
+        }
+
+        #[cfg(feature = "alloc")]
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Symbol, E> {
+            Ok(Symbol::new(v))
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Symbol, E> {
+            Symbol::try_new_borrowed(v).map_err(E::custom)
+        }
+
+        // Reuses the string as the storage of a new symbol.
+        #[cfg(feature = "alloc")]
+        fn visit_string<E: serde::de::Error>(self, mut v: String) -> Result<Symbol, E> {
+            Ok(Symbol::new_take(&mut v))
+        }
+
+        fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Symbol, E> {
+            let s = core::str::from_utf8(v)
+                .map_err(|_| E::invalid_value(serde::de::Unexpected::Bytes(v), &self))?;
+            self.visit_str(s)
+        }
+    }
+};
+
+#[cfg(feature = "compact_str")]
+const _: () = {
+    use compact_str::CompactString;
+
+    impl From<CompactString> for Symbol {
+        #[inline]
+        fn from(value: CompactString) -> Self {
+            Symbol::new(&*value)
+        }
+    }
+
+    impl From<Symbol> for CompactString {
+        /// Does not allocate, because the string of a symbol is `'static`.
+        #[inline]
+        fn from(value: Symbol) -> Self {
+            CompactString::const_new(value.as_str())
+        }
+    }
+};
+
+#[cfg(feature = "smol_str")]
+const _: () = {
+    use smol_str::SmolStr;
+
+    impl From<SmolStr> for Symbol {
+        #[inline]
+        fn from(value: SmolStr) -> Self {
+            Symbol::new(&*value)
+        }
+    }
+
+    impl From<Symbol> for SmolStr {
+        /// Does not allocate, because the string of a symbol is `'static`.
+        #[inline]
+        fn from(value: Symbol) -> Self {
+            SmolStr::new_static(value.as_str())
         }
     }
 };
@@ -0,0 +1,52 @@
+use core::{cell::RefCell, hash::BuildHasher};
+
+use crate::{BuildSymbolHasher, Registry, Symbol};
+
+/// Number of recently used symbols remembered by each thread.
+const CACHE_SIZE: usize = 16;
+
+std::thread_local! {
+    /// Recently used symbols with the hash of their string, most recently used
+    /// first.
+    static CACHE: RefCell<[Option<(u64, Symbol)>; CACHE_SIZE]> =
+        const { RefCell::new([None; CACHE_SIZE]) };
+}
+
+/// Get or insert a symbol through the thread-local cache, which avoids taking
+/// the registry lock for strings that were recently used on this thread.
+///
+/// Cache entries are only a memo, validated by comparing the string, so hash
+/// collisions cannot produce wrong results.
+pub(crate) fn get_or_insert(string: &str) -> Symbol {
+    let hash = BuildSymbolHasher::default().hash_one(string);
+
+    let hit = CACHE
+        .try_with(|cache| {
+            let mut cache = cache.try_borrow_mut().ok()?;
+            let index = cache.iter().position(|entry| {
+                entry.is_some_and(|(entry_hash, symbol)| {
+                    entry_hash == hash && symbol.as_str() == string
+                })
+            })?;
+            // Move to front.
+            cache[..=index].rotate_right(1);
+            cache[0].map(|(_, symbol)| symbol)
+        })
+        .ok()
+        .flatten();
+    if let Some(symbol) = hit {
+        return symbol;
+    }
+
+    let symbol = Registry::global().get_or_insert(string);
+    // Ignore errors: The cache may be unavailable while the thread is being
+    // destroyed.
+    _ = CACHE.try_with(|cache| {
+        if let Ok(mut cache) = cache.try_borrow_mut() {
+            // Evict the least recently used entry.
+            cache.rotate_right(1);
+            cache[0] = Some((hash, symbol));
+        }
+    });
+    symbol
+}
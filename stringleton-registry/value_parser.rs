@@ -0,0 +1,91 @@
+use std::{boxed::Box, ffi::OsStr, vec::Vec};
+
+use clap::builder::{
+    PossibleValue, PossibleValuesParser, StringValueParser, TypedValueParser, ValueParserFactory,
+};
+
+use crate::Symbol;
+
+/// `clap` value parser producing [`Symbol`]s.
+///
+/// This is the default value parser for `Symbol` arguments, so
+/// `#[arg(value_parser)] name: Symbol` works without naming it.
+///
+/// By default, any UTF-8 value is accepted and interned with [`Symbol::new()`].
+/// Like all symbols, these are never freed, so a free-form `Symbol` argument
+/// leaks memory for every distinct value it receives. This is rarely a problem
+/// for command line arguments, but for CLIs that may be invoked with untrusted
+/// input (e.g. from a server), prefer restricting the values to a closed set
+/// with [`known()`](Self::known):
+///
+/// ```rust,ignore
+/// #[arg(value_parser = SymbolValueParser::known([sym!(fast), sym!(safe)]))]
+/// mode: Symbol,
+/// ```
+///
+/// With a closed set, values outside of the set are rejected without
+/// interning them, with the usual "possible values" error from `clap`, and the
+/// possible values are listed in the help text.
+#[derive(Clone, Debug, Default)]
+pub struct SymbolValueParser {
+    known: Option<(Vec<Symbol>, PossibleValuesParser)>,
+}
+
+impl SymbolValueParser {
+    /// Create a parser that accepts any value.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a parser that only accepts the given symbols.
+    #[must_use]
+    pub fn known(symbols: impl IntoIterator<Item = Symbol>) -> Self {
+        let symbols: Vec<Symbol> = symbols.into_iter().collect();
+        let parser = PossibleValuesParser::new(symbols.iter().map(Symbol::as_str));
+        Self {
+            known: Some((symbols, parser)),
+        }
+    }
+}
+
+impl TypedValueParser for SymbolValueParser {
+    type Value = Symbol;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Symbol, clap::Error> {
+        let Some((symbols, parser)) = &self.known else {
+            let value = StringValueParser::new().parse_ref(cmd, arg, value)?;
+            return Ok(Symbol::from(value));
+        };
+
+        let value = parser.parse_ref(cmd, arg, value)?;
+        // The value may differ from the symbol in case if the argument
+        // ignores case, so find the symbol it matched.
+        let ignore_case = arg.is_some_and(clap::Arg::is_ignore_case_set);
+        Ok(*symbols
+            .iter()
+            .find(|symbol| PossibleValue::new(symbol.as_str()).matches(&value, ignore_case))
+            .expect("the value was matched by `PossibleValuesParser`"))
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        self.known
+            .as_ref()
+            .and_then(|(_, parser)| parser.possible_values())
+    }
+}
+
+impl ValueParserFactory for Symbol {
+    type Parser = SymbolValueParser;
+
+    #[inline]
+    fn value_parser() -> SymbolValueParser {
+        SymbolValueParser::new()
+    }
+}
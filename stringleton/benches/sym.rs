@@ -0,0 +1,21 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use stringleton::{StaticSymbol, Symbol, static_sym, sym};
+
+stringleton::enable!();
+
+fn access(c: &mut Criterion) {
+    const STATIC: StaticSymbol = static_sym!(bench_static_sym);
+
+    let mut group = c.benchmark_group("access a literal symbol");
+    group.bench_function("sym!()", |b| b.iter(|| sym!(bench_sym)));
+    group.bench_function("static_sym!()", |b| b.iter(|| *black_box(STATIC)));
+    // Baseline: Looking up the same symbol at runtime.
+    group.bench_function("Symbol::new (hit)", |b| {
+        b.iter(|| Symbol::new(black_box("bench_sym")));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, access);
+criterion_main!(benches);
@@ -1,6 +1,35 @@
 #![doc = include_str!("README.md")]
 
-pub use stringleton_registry::{Registry, StaticSymbol, Symbol};
+#[cfg(feature = "derive")]
+pub use stringleton_derive::SymbolEnum;
+#[cfg(feature = "profiling")]
+pub use stringleton_registry::AccessStats;
+#[cfg(feature = "dashmap")]
+pub use stringleton_registry::ConcurrentSymbolMap;
+#[cfg(feature = "phf")]
+pub use stringleton_registry::PerfectSymbolSet;
+#[cfg(feature = "std")]
+pub use stringleton_registry::SymbolMap;
+#[cfg(all(feature = "serde", feature = "alloc"))]
+pub use stringleton_registry::SymbolSeq;
+#[cfg(feature = "clap")]
+pub use stringleton_registry::SymbolValueParser;
+#[cfg(feature = "capi")]
+pub use stringleton_registry::capi;
+#[cfg(feature = "serde")]
+pub use stringleton_registry::serde_known;
+pub use stringleton_registry::{
+    BatchInterner, BuildSymbolHasher, ByStr, InternedString, InvalidSymbolFfi, NotInterned,
+    OptionSymbolExt, Registry, StartupReport, StaticArena, StaticSymbol, StrKeyed, Symbol,
+    SymbolFfi, SymbolHasher,
+};
+#[cfg(feature = "alloc")]
+pub use stringleton_registry::{
+    FuzzRegistry, FuzzSymbol, GlobalSymbolAllocator, InternError, LeakingSymbolAllocator,
+    MissingSymbols, ScopedPool, ScopedSymbol, SymbolBitSet, SymbolBuilder,
+};
+#[cfg(feature = "custom-lock")]
+pub use stringleton_registry::{RawRegistryLock, custom_registry_lock};
 
 /// Create a literal symbol from a literal identifier or string
 ///
@@ -65,9 +94,9 @@ macro_rules! sym {
     };
     (@impl $sym:expr) => {{
         // Note: Using `crate` to refer to the calling crate - this is deliberate.
-        #[cfg_attr(not(any(miri, target_arch = "wasm32")), $crate::internal::linkme::distributed_slice(crate::_stringleton_enabled::TABLE))]
+        #[cfg_attr(not(any(miri, target_arch = "wasm32")), $crate::internal::linkme::distributed_slice(crate::_stringleton_did_you_forget_to_call_enable_in_the_crate_root::TABLE))]
         #[cfg_attr(not(any(miri, target_arch = "wasm32")), linkme(crate = $crate::internal::linkme))]
-        static SITE: $crate::internal::Site = $crate::internal::Site::new(&$sym);
+        static SITE: $crate::internal::Site = $crate::internal::Site::new_at(&$sym, ::core::file!(), ::core::line!());
         unsafe {
             // SAFETY: This site will be initialized by the static ctor because
             // it participates in the distributed slice.
@@ -76,6 +105,67 @@ macro_rules! sym {
     }
 }
 
+/// Create a literal symbol from the value of an environment variable at
+/// compile time.
+///
+/// This works like [`sym!(...)`](crate::sym), with the string being the result
+/// of `env!(...)`, so it has the same (lack of) overhead. A missing environment
+/// variable is a compile error.
+///
+/// ```rust,ignore
+/// let version: Symbol = sym_env!("CARGO_PKG_VERSION");
+/// ```
+#[macro_export]
+macro_rules! sym_env {
+    ($var:literal) => {
+        $crate::sym!(@impl ::core::env!($var))
+    };
+}
+
+/// Create a symbol at runtime from a format string, like `format!(...)`.
+///
+/// This is shorthand for
+/// [`Symbol::new_fmt(format_args!(...))`](crate::Symbol::new_fmt), which
+/// avoids copying the formatted string when the symbol is new. Like
+/// [`Symbol::new()`](crate::Symbol::new), symbols created this way are never
+/// freed, so avoid using this with unbounded input.
+///
+/// ```rust,ignore
+/// let layer: Symbol = sym_fmt!("layer_{}", index);
+/// ```
+#[macro_export]
+#[cfg(feature = "alloc")]
+macro_rules! sym_fmt {
+    ($($arg:tt)*) => {
+        $crate::Symbol::new_fmt(::core::format_args!($($arg)*))
+    };
+}
+
+/// Assert that two symbols are the same symbol.
+///
+/// This works like `assert_eq!(left, right)`, but on failure, the message
+/// includes the FFI handles of both symbols, and points out when the strings
+/// are equal. That happens when the symbols come from different registries,
+/// which is the classic symptom of a broken dynamic linking setup. See
+/// [`Symbol::assert_same()`](crate::Symbol::assert_same).
+///
+/// Both arguments can be anything that converts into a `Symbol`, including a
+/// [`StaticSymbol`].
+///
+/// ```rust,ignore
+/// assert_same_symbol!(plugin_symbol(), sym!(render));
+/// ```
+#[macro_export]
+#[cfg(feature = "test-util")]
+macro_rules! assert_same_symbol {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::Symbol::assert_same(
+            ::core::convert::Into::<$crate::Symbol>::into($left),
+            ::core::convert::Into::<$crate::Symbol>::into($right),
+        )
+    };
+}
+
 /// Create a static location for a literal symbol.
 ///
 /// This macro works the same as [`sym!(...)`](crate::sym), except that it
@@ -96,6 +186,34 @@ macro_rules! sym {
 /// assert_eq!(OTHER_SYMBOL, sym("abc"));
 /// ```
 ///
+/// The macro is a plain constant expression, so it also works in array and
+/// struct initializers, `const fn` bodies, associated constants (including
+/// trait defaults and impls with generic parameters), and array repeat
+/// expressions. `StaticSymbol` cannot be the type of a const generic parameter,
+/// because only integers, `bool`, and `char` are allowed there on stable Rust.
+///
+/// # Matching
+///
+/// Symbols cannot be used as patterns, because their values are only known at
+/// runtime. Instead, declare well-known symbols as constants and compare
+/// against them in match guards, `matches!()`, or `if let` chains. The
+/// comparison is a pointer comparison through the extra indirection described
+/// below:
+///
+/// ```rust,ignore
+/// const ADD: StaticSymbol = static_sym!(add);
+/// const SUB: StaticSymbol = static_sym!(sub);
+///
+/// let op = match symbol {
+///     s if s == ADD => Op::Add,
+///     s if s == SUB => Op::Sub,
+///     _ => return None,
+/// };
+/// assert!(matches!(symbol, s if s == ADD || s == SUB));
+/// ```
+///
+/// `sym!(...)` can also be used directly in a guard, with the same cost.
+///
 /// # Use case
 ///
 /// Use this macro to avoid having too many "magic symbols" in your code
@@ -133,9 +251,9 @@ macro_rules! static_sym {
                 // Tiny function just to get the `Site` for this symbol.
                 fn _stringleton_static_symbol_call_site() -> &'static $crate::internal::Site {
                     // Note: Using `crate` to refer to the calling crate - this is deliberate.
-                    #[cfg_attr(not(any(miri, target_arch = "wasm32")), $crate::internal::linkme::distributed_slice(crate::_stringleton_enabled::TABLE))]
+                    #[cfg_attr(not(any(miri, target_arch = "wasm32")), $crate::internal::linkme::distributed_slice(crate::_stringleton_did_you_forget_to_call_enable_in_the_crate_root::TABLE))]
                     #[cfg_attr(not(any(miri, target_arch = "wasm32")), linkme(crate = $crate::internal::linkme))]
-                    static SITE: $crate::internal::Site = $crate::internal::Site::new(&$sym);
+                    static SITE: $crate::internal::Site = $crate::internal::Site::new_at(&$sym, ::core::file!(), ::core::line!());
                     &SITE
                 }
                 _stringleton_static_symbol_call_site
@@ -144,6 +262,203 @@ macro_rules! static_sym {
     }}
 }
 
+/// Register a table of symbols at startup.
+///
+/// The argument is an expression of type `&[&'static &'static str]`, typically
+/// a `static` in generated code. All of its strings are registered in a static
+/// initializer, using [`Registry::preload()`](crate::Registry::preload), so
+/// that [`Symbol::get()`](crate::Symbol::get) succeeds for each of them from
+/// the start of `main()`.
+///
+/// ```rust,ignore
+/// static KNOWN_SYMBOLS: &[&&str] = &[&"position", &"velocity", &"mass"];
+/// stringleton::preload_symbols!(KNOWN_SYMBOLS);
+/// ```
+///
+/// Compared to [`sym!(...)`](crate::sym) call sites, a flat table of strings
+/// takes less space in the binary, but the symbols must be looked up at
+/// runtime. This macro may be invoked any number of times, and does not
+/// require [`enable!()`](crate::enable).
+///
+/// **CAUTION:** On targets without static initializers (Miri and `wasm32`),
+/// this macro does nothing, and `Registry::preload()` must be called
+/// manually.
+#[macro_export]
+macro_rules! preload_symbols {
+    ($table:expr) => {
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        const _: () = {
+            $crate::internal::ctor::declarative::ctor! {
+                #[ctor]
+                fn _stringleton_preload_symbols() {
+                    $crate::internal::Registry::global().preload($table);
+                }
+            }
+        };
+    };
+}
+
+/// Iterate over the symbols used by the calling crate.
+///
+/// This expands to an iterator over the symbols of every
+/// [`sym!(...)`](crate::sym) and [`static_sym!(...)`](crate::static_sym) call
+/// site in the calling crate (i.e., the symbol table created by
+/// [`enable!()`](crate::enable)), in no particular order. Symbols that are used
+/// at more than one call site appear more than once. Unlike
+/// [`Registry::read()`](crate::Registry::read), which contains the symbols of
+/// all crates, this can be used to audit the symbols of a single crate.
+///
+/// ```rust,ignore
+/// let _ = sym!(hello);
+/// assert!(stringleton::crate_symbols!().any(|symbol| symbol == "hello"));
+/// ```
+///
+/// Crates that use `enable!(other_crate)` share the symbol table of
+/// `other_crate`, so both crates see the call sites of both crates.
+///
+/// The macro is not available with `enable!(manual)` (or when sharing the
+/// table of a crate that uses it), because nothing guarantees that the symbols
+/// have been registered when the iterator is used:
+///
+/// ```compile_fail,E0425
+/// stringleton::enable!(manual);
+///
+/// fn main() {
+///     let _ = stringleton::crate_symbols!();
+/// }
+/// ```
+///
+/// **CAUTION:** On targets without static initializers (Miri and `wasm32`),
+/// there is no symbol table, and the iterator is always empty.
+#[macro_export]
+#[allow(clippy::crate_in_macro_def)]
+macro_rules! crate_symbols {
+    () => {{
+        // Note: Using `crate` to refer to the calling crate - this is deliberate.
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        let table: &'static [$crate::internal::Site] = {
+            use crate::_stringleton_did_you_forget_to_call_enable_in_the_crate_root as enabled;
+            // Only defined by `enable!()` without `manual`.
+            let () = enabled::CRATE_SYMBOLS_IS_NOT_AVAILABLE_WITH_ENABLE_MANUAL;
+            &enabled::TABLE
+        };
+        #[cfg(any(miri, target_arch = "wasm32"))]
+        let table: &'static [$crate::internal::Site] = &[];
+        unsafe {
+            // SAFETY: The table is registered by the static ctor installed by
+            // `enable!()`, as checked above. Without static ctors, the table
+            // is empty.
+            $crate::internal::crate_symbols(table)
+        }
+    }};
+}
+
+/// Dispatch on a symbol by comparing it against constants, like a `match`.
+///
+/// `Symbol` cannot be used in `match` patterns, because its value is only known
+/// at runtime. This macro provides the same syntax for comparing a symbol
+/// against [`static_sym!(...)`](crate::static_sym) constants (or any other
+/// path to a `Symbol` or `StaticSymbol`):
+///
+/// ```rust,ignore
+/// const CLICK: StaticSymbol = static_sym!(click);
+/// const HOVER: StaticSymbol = static_sym!(hover);
+/// const PRESS: StaticSymbol = static_sym!(press);
+///
+/// let action = match_symbol!(event, {
+///     CLICK | PRESS => "activate",
+///     HOVER => "highlight",
+///     _ => "ignore",
+/// });
+/// ```
+///
+/// # Syntax
+///
+/// `match_symbol!(<expr>, { <arms> })`, where `<expr>` evaluates to a
+/// [`Symbol`], and each arm is `<path> [| <path> ...] => <expr>`. Arms are
+/// separated by commas. The last arm may be the default arm `_ => <expr>`.
+/// Without a default arm, all arms must evaluate to `()`.
+///
+/// The arms are checked in order, and the first matching arm is evaluated. The
+/// expansion is a chain of `if`/`else` pointer comparisons, so each comparison
+/// is as cheap as comparing two symbols.
+#[macro_export]
+macro_rules! match_symbol {
+    ($symbol:expr, { $($arms:tt)* }) => {{
+        let symbol: $crate::Symbol = $symbol;
+        $crate::_stringleton_match_symbol_arms!(symbol; $($arms)*)
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _stringleton_match_symbol_arms {
+    ($symbol:ident;) => {
+        ()
+    };
+    ($symbol:ident; _ => $default:expr $(,)?) => {
+        $default
+    };
+    ($symbol:ident; $($pat:path)|+ => $body:expr $(, $($rest:tt)*)?) => {
+        if $($symbol == $pat)||+ {
+            $body
+        } else {
+            $crate::_stringleton_match_symbol_arms!($symbol; $($($rest)*)?)
+        }
+    };
+}
+
+/// Declare a compile-time perfect hash map from strings to values.
+///
+/// This builds a [`PerfectSymbolSet`] at compile time using [`phf`](https://docs.rs/phf),
+/// which maps symbols to values with a single hash of the symbol's string and
+/// no registry lock. The keys must be string literals, and the values must be
+/// constant expressions.
+///
+/// ```rust,ignore
+/// #[derive(Debug, PartialEq)]
+/// enum Op {
+///     Add,
+///     Sub,
+/// }
+///
+/// perfect_symbol_set! {
+///     static BUILTINS: PerfectSymbolSet<Op> = {
+///         "add" => Op::Add,
+///         "sub" => Op::Sub,
+///     };
+/// }
+///
+/// assert_eq!(BUILTINS.lookup(sym!(add)), Some(&Op::Add));
+/// assert_eq!(BUILTINS.lookup(sym!(mul)), None);
+/// ```
+///
+/// This is only meant for static, closed sets of strings, like the keywords
+/// of a language. The set cannot be changed at runtime. For small sets,
+/// [`match_symbol!`](crate::match_symbol) with [`static_sym!`](crate::static_sym)
+/// constants as arms may be faster, because it only compares pointers.
+///
+/// Requires the `phf` feature.
+#[macro_export]
+#[cfg(feature = "phf")]
+macro_rules! perfect_symbol_set {
+    ($(
+        $(#[$attr:meta])*
+        $vis:vis static $name:ident: PerfectSymbolSet<$ty:ty> = {
+            $($key:literal => $value:expr),* $(,)?
+        };
+    )*) => {$(
+        $(#[$attr])*
+        $vis static $name: $crate::PerfectSymbolSet<$ty> = {
+            // `phf_map!` refers to `phf` by name.
+            use $crate::internal::phf;
+            $crate::PerfectSymbolSet::from_phf(phf::phf_map! {
+                $($key => $value),*
+            })
+        };
+    )*};
+}
+
 /// Enable the [`sym!(...)`](crate::sym) macro in the calling crate.
 ///
 /// Put a call to this macro somewhere in the root of each crate that uses the
@@ -171,10 +486,62 @@ macro_rules! static_sym {
 /// causes symbols in this crate to be registered as part of symbols in the
 /// other crate.
 ///
+/// Multiple crates can be given as a comma-separated list, like
+/// `enable!(crate_a, crate_b)`. Symbols in this crate are registered as part of
+/// the first crate, and the tables of the remaining crates are re-exported
+/// under hidden `_stringleton_forwarded` modules, which keeps them linked
+/// into the current crate. The paths must be absolute (for example crate
+/// names), because the re-exports live in nested modules.
+///
 /// **CAUTION:** Using the second variant is discouraged, because it will not
 /// work when the other crate is being loaded as a dynamic library. However, it
 /// is very slightly more efficient.
 ///
+/// The first variant must appear exactly once per crate, at the crate root.
+/// Using it more than once is a compile error that names the problem:
+///
+/// ```compile_fail,E0428
+/// stringleton::enable!();
+/// stringleton::enable!();
+/// # fn main() {}
+/// ```
+///
+/// Forgetting to call this macro in a crate that uses `sym!()` is also a
+/// compile error, which mentions `did_you_forget_to_call_enable_in_the_crate_root`:
+///
+/// ```compile_fail,E0433
+/// let symbol = stringleton::sym!(hello);
+/// ```
+///
+/// ## Manual registration
+///
+/// `enable!(manual)` builds the same symbol table, but does not install a
+/// static constructor to register it. Instead, the crate must call the
+/// generated `_stringleton_register_symbols()` function at its root itself,
+/// for example at the start of `main()`:
+///
+/// ```rust,ignore
+/// stringleton::enable!(manual);
+///
+/// fn main() {
+///     unsafe {
+///         // SAFETY: No `sym!()` or `static_sym!()` in this crate has been
+///         // used yet, and no other threads are running.
+///         _stringleton_register_symbols();
+///     }
+///     assert_eq!(sym!(hello), "hello");
+/// }
+/// ```
+///
+/// This is for platforms where static constructors are not supported, or run
+/// in an order that cannot be controlled. It moves the responsibility for the
+/// ordering to the caller: Every `sym!()` and `static_sym!()` in the crate
+/// must only be evaluated after the function has returned. Using them earlier
+/// is undefined behavior, which the `debug-assertions` feature turns into a
+/// panic. The function must not be called while another thread may be
+/// evaluating `sym!()` in the crate, which is why it is `unsafe`. Calling it
+/// more than once is harmless.
+///
 /// ## Why?
 ///
 /// The reason that this macro is necessary is dynamic linking. Under "normal"
@@ -193,6 +560,15 @@ macro_rules! static_sym {
 #[macro_export]
 macro_rules! enable {
     () => {
+        // A second `enable!()` in the same module produces a "defined multiple
+        // times" error naming the problem. This must come before the
+        // distributed slice, whose own duplicate error is much less helpful.
+        // It is a private item, so it does not become part of the public API
+        // of the crate.
+        #[allow(non_upper_case_globals, dead_code)]
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        const _stringleton_enable_must_appear_exactly_once_per_crate: () = ();
+
         #[doc(hidden)]
         #[cfg(not(any(miri, target_arch = "wasm32")))]
         pub mod _stringleton_enabled {
@@ -211,26 +587,274 @@ macro_rules! enable {
                     }
                 }
             }
+
+            /// Tells `crate_symbols!()` that `TABLE` is registered by the
+            /// static ctor above, which `enable!(manual)` does not install.
+            #[doc(hidden)]
+            pub const CRATE_SYMBOLS_IS_NOT_AVAILABLE_WITH_ENABLE_MANUAL: () = ();
         }
 
         #[allow(unused)]
         #[doc(hidden)]
         #[cfg(not(any(miri, target_arch = "wasm32")))]
         pub use _stringleton_enabled::_stringleton_register_symbols;
+
+        // `sym!()` refers to the table through this name, so that forgetting
+        // to call `enable!()` produces an error message that names the problem.
+        #[doc(hidden)]
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        pub use _stringleton_enabled as _stringleton_did_you_forget_to_call_enable_in_the_crate_root;
+    };
+    (manual) => {
+        #[allow(non_upper_case_globals, dead_code)]
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        const _stringleton_enable_must_appear_exactly_once_per_crate: () = ();
+
+        #[doc(hidden)]
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        pub mod _stringleton_enabled {
+            #[$crate::internal::linkme::distributed_slice]
+            #[linkme(crate = $crate::internal::linkme)]
+            #[doc(hidden)]
+            pub static TABLE: [$crate::internal::Site] = [..];
+
+            /// Register the symbols of this crate. See `enable!(manual)`.
+            ///
+            /// # Safety
+            ///
+            /// Must be called before any `sym!()` or `static_sym!()` in this
+            /// crate is evaluated, and not while other threads may be
+            /// evaluating them.
+            #[doc(hidden)]
+            pub unsafe fn _stringleton_register_symbols() {
+                unsafe {
+                    // SAFETY: Preconditions of this function.
+                    $crate::internal::Registry::register_sites(&TABLE);
+                }
+            }
+        }
+
+        #[allow(unused)]
+        #[doc(hidden)]
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        pub use _stringleton_enabled::_stringleton_register_symbols;
+
+        /// Call sites initialize themselves on first use when static
+        /// constructors are not supported, so there is nothing to register.
+        #[allow(unused, clippy::missing_safety_doc)]
+        #[doc(hidden)]
+        #[cfg(any(miri, target_arch = "wasm32"))]
+        pub unsafe fn _stringleton_register_symbols() {}
+
+        #[doc(hidden)]
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        pub use _stringleton_enabled as _stringleton_did_you_forget_to_call_enable_in_the_crate_root;
     };
     ($($krate:tt)+) => {
+        $crate::_stringleton_enable_forward!([] $($krate)+);
+
         #[doc(hidden)]
         #[cfg(not(any(miri, target_arch = "wasm32")))]
-        pub use $($krate)*::_stringleton_enabled;
+        pub use _stringleton_enabled as _stringleton_did_you_forget_to_call_enable_in_the_crate_root;
     };
 }
 
+/// Implementation of `enable!(krate, ...)`.
+///
+/// Splits the comma-separated list of crate paths. The first one provides the
+/// `_stringleton_enabled` module used by this crate, and each following one is
+/// re-exported in a nested `_stringleton_forwarded` module, so the names never
+/// collide.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _stringleton_enable_forward {
+    ([$($krate:tt)+] $(,)?) => {
+        #[doc(hidden)]
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        pub use $($krate)+::_stringleton_enabled;
+    };
+    ([$($krate:tt)+] , $($rest:tt)+) => {
+        #[doc(hidden)]
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        pub use $($krate)+::_stringleton_enabled;
+
+        #[doc(hidden)]
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        pub mod _stringleton_forwarded {
+            $crate::_stringleton_enable_forward!([] $($rest)+);
+        }
+    };
+    ([$($krate:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::_stringleton_enable_forward!([$($krate)* $next] $($rest)*);
+    };
+}
+
+/// Declare distinct types of symbols.
+///
+/// Each declaration generates a `#[repr(transparent)]` wrapper around a
+/// [`Symbol`], so symbols used as different kinds of keys cannot be mixed up:
+///
+/// ```rust,ignore
+/// symbol_newtype! {
+///     /// The name of a component type.
+///     pub struct ComponentName;
+///     pub struct EntityName;
+/// }
+///
+/// let transform = ComponentName::new("transform");
+/// let player = EntityName::new("player");
+/// assert_eq!(transform, ComponentName::from_symbol(sym!(transform)));
+/// // transform == player; // Error: mismatched types
+/// ```
+///
+/// The wrapper is `Copy`, and derives `PartialEq`, `Eq`, `Hash`, `PartialOrd`,
+/// and `Ord` from [`Symbol`], so comparisons are still pointer comparisons
+/// (and orders by address, like `Symbol`). Its `Display` and `Debug`
+/// implementations print the string. It has the following methods:
+///
+/// - `new(string)`, like [`Symbol::new()`] (requires the `alloc` feature).
+/// - `new_static(string)`, like [`Symbol::new_static()`].
+/// - `get(string)`, like [`Symbol::get()`].
+/// - `const fn from_symbol(symbol)`, to wrap a symbol, for example from
+///   `sym!()`.
+/// - `const fn symbol(&self)`, to unwrap the symbol.
+/// - `const fn as_str(&self)`, like [`Symbol::as_str()`].
+///
+/// It also implements `From<T> for Symbol` and `AsRef<str>`. Converting
+/// a plain symbol to a wrapper must be explicit, using `from_symbol()`.
+#[macro_export]
+macro_rules! symbol_newtype {
+    ($(
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident;
+    )*) => {$(
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[repr(transparent)]
+        $vis struct $name($crate::Symbol);
+
+        // Not all of the methods are used for every type.
+        #[allow(dead_code)]
+        impl $name {
+            /// Wrap `symbol`.
+            #[inline]
+            #[must_use]
+            pub const fn from_symbol(symbol: $crate::Symbol) -> Self {
+                Self(symbol)
+            }
+
+            /// Create a symbol from a static string. See
+            /// `Symbol::new_static()`.
+            #[inline]
+            #[must_use]
+            pub fn new_static(string: &'static &'static str) -> Self {
+                Self($crate::Symbol::new_static(string))
+            }
+
+            /// Get the symbol for `string`, if it has been registered. See
+            /// `Symbol::get()`.
+            #[inline]
+            #[must_use]
+            pub fn get(string: impl ::core::convert::AsRef<str>) -> ::core::option::Option<Self> {
+                $crate::Symbol::get(string).map(Self)
+            }
+
+            /// The wrapped symbol.
+            #[inline]
+            #[must_use]
+            pub const fn symbol(&self) -> $crate::Symbol {
+                self.0
+            }
+
+            /// The string of the symbol.
+            #[inline]
+            #[must_use]
+            pub const fn as_str(&self) -> &'static str {
+                self.0.as_str()
+            }
+        }
+
+        $crate::_stringleton_symbol_newtype_new!($name);
+
+        impl ::core::convert::From<$name> for $crate::Symbol {
+            #[inline]
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl ::core::convert::AsRef<str> for $name {
+            #[inline]
+            fn as_ref(&self) -> &str {
+                self.0.as_str()
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Debug::fmt(&self.0, f)
+            }
+        }
+    )*};
+}
+
+/// Generates `new()` for `symbol_newtype!`, which is only available with the
+/// `alloc` feature of this crate (not of the calling crate).
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "alloc")]
+macro_rules! _stringleton_symbol_newtype_new {
+    ($name:ident) => {
+        #[allow(dead_code)]
+        impl $name {
+            /// Create a symbol at runtime. See `Symbol::new()`.
+            #[inline]
+            #[must_use]
+            pub fn new(string: impl ::core::convert::AsRef<str>) -> Self {
+                Self($crate::Symbol::new(string))
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "alloc"))]
+macro_rules! _stringleton_symbol_newtype_new {
+    ($name:ident) => {};
+}
+
 #[doc(hidden)]
 pub mod internal {
     pub use ctor;
     pub use linkme;
     pub use stringleton_registry::Registry;
     pub use stringleton_registry::Site;
+    #[cfg(feature = "phf")]
+    pub use stringleton_registry::phf;
+
+    /// Iterate over the symbols of a crate's symbol table.
+    ///
+    /// # Safety
+    ///
+    /// `table` must be the symbol table of a crate, and its symbols must have
+    /// been registered.
+    pub unsafe fn crate_symbols(
+        table: &'static [Site],
+    ) -> impl ExactSizeIterator<Item = crate::Symbol> + Clone + use<> {
+        table.iter().map(|site| unsafe {
+            // SAFETY: Preconditions of this function.
+            site.get_after_ctor()
+        })
+    }
 }
 
 #[cfg(test)]
@@ -283,6 +907,123 @@ mod tests {
         assert_eq!(t, "SYM_CACHE");
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn child() {
+        let ui = sym!("app.ui");
+        assert_eq!(ui.child("button", '.'), sym!("app.ui.button"));
+        assert_eq!(ui.child("button", '.'), ui.child("button", '.'));
+        assert_eq!(sym!(app).child("ui", '.'), ui);
+        assert_eq!(ui.child("ünï", '→'), "app.ui→ünï");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn split() {
+        let parts: Vec<Symbol> = sym!("system.render.shadow").split('.').collect();
+        assert_eq!(parts, [sym!(system), sym!(render), sym!(shadow)]);
+        assert_eq!(sym!(system).split('.').collect::<Vec<_>>(), [sym!(system)]);
+        assert_eq!(
+            sym!("a..b").split('.').collect::<Vec<_>>(),
+            [sym!(a), sym!(""), sym!(b)]
+        );
+    }
+
+    #[test]
+    fn option_eq_str() {
+        use super::OptionSymbolExt;
+
+        assert!(Some(sym!(a)).eq_str("a"));
+        assert!(!Some(sym!(a)).eq_str("b"));
+        assert!(!None::<Symbol>.eq_str("a"));
+        assert!(!None::<Symbol>.eq_str(""));
+        assert!(Some(sym!("")).eq_str(""));
+    }
+
+    #[test]
+    fn strip_prefix_suffix() {
+        assert_eq!(sym!("on_click").strip_prefix("on_"), Some("click"));
+        assert_eq!(sym!("on_click").strip_prefix("off_"), None);
+        assert_eq!(sym!("on_click").strip_suffix("_click"), Some("on"));
+        assert_eq!(sym!("on_click").strip_suffix("_hover"), None);
+    }
+
+    #[test]
+    fn match_symbol() {
+        const CLICK: StaticSymbol = static_sym!(click);
+        const HOVER: StaticSymbol = static_sym!(hover);
+        const PRESS: StaticSymbol = static_sym!(press);
+
+        let dispatch = |event: Symbol| {
+            match_symbol!(event, {
+                CLICK => 1,
+                HOVER => 2,
+                PRESS => 3,
+                _ => 0,
+            })
+        };
+        assert_eq!(dispatch(sym!(click)), 1);
+        assert_eq!(dispatch(sym!(hover)), 2);
+        assert_eq!(dispatch(sym!(press)), 3);
+        assert_eq!(dispatch(sym!(scroll)), 0);
+
+        let mut hits = 0;
+        match_symbol!(sym!(press), {
+            CLICK | PRESS => hits += 1,
+            HOVER => {}
+        });
+        assert_eq!(hits, 1);
+    }
+
+    static PRELOADED: &[&&str] = &[&"preloaded.a", &"preloaded.b"];
+    preload_symbols!(PRELOADED);
+
+    #[test]
+    #[cfg(not(any(miri, target_arch = "wasm32")))]
+    fn preload_symbols() {
+        let a = Symbol::get("preloaded.a").unwrap();
+        assert!(core::ptr::eq(a.inner(), PRELOADED[0]));
+        assert!(Symbol::get("preloaded.b").is_some());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn sym_fmt() {
+        let index = 3;
+        let symbol = sym_fmt!("layer_{index}");
+        assert_eq!(symbol, sym!(layer_3));
+        assert_eq!(sym_fmt!("layer_{}", 1 + 2), symbol);
+        assert_eq!(sym_fmt!("layer_3"), symbol);
+    }
+
+    #[test]
+    fn sym_env() {
+        let name = sym_env!("CARGO_PKG_NAME");
+        assert_eq!(name, "stringleton");
+        assert_eq!(name, sym!(stringleton));
+    }
+
+    #[test]
+    #[cfg(feature = "derive")]
+    fn symbol_enum_crate_path() {
+        mod renamed {
+            pub use crate::*;
+        }
+
+        #[derive(super::SymbolEnum, Debug, PartialEq)]
+        #[symbol(crate = renamed)]
+        enum Event {
+            #[symbol = "up"]
+            MouseUp,
+            MouseDown,
+        }
+
+        assert_eq!(Event::MouseUp.symbol(), sym!(up));
+        assert_eq!(Event::MouseDown.symbol(), sym!(mouse_down));
+        assert_eq!(Event::from_symbol(sym!(up)), Some(Event::MouseUp));
+        assert_eq!(Event::from_symbol(sym!(mouse_up)), None);
+    }
+
     #[test]
     fn statics() {
         static A: StaticSymbol = static_sym!(a);
@@ -293,4 +1034,308 @@ mod tests {
         assert_ne!(A2, sym!(b));
         assert_eq!(C, sym!(c));
     }
+
+    #[test]
+    fn statics_eq_str() {
+        const A: StaticSymbol = static_sym!(a);
+        const B: StaticSymbol = static_sym!(b);
+        assert_eq!(A, "a");
+        assert_eq!(A, *"a");
+        assert_eq!("a", A);
+        assert_eq!(*"a", A);
+        assert_ne!(A, "b");
+        assert_ne!("b", A);
+        assert!(A < "b");
+        assert!(A < *"b");
+        assert!("a" < B);
+        assert!(*"a" < B);
+        assert!(B > "a");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_known() {
+        use serde::de::{IntoDeserializer, value::Error};
+        const MODES: [StaticSymbol; 2] = [static_sym!(known_fast), static_sym!(known_safe)];
+        let deserialize = |string: &str| {
+            super::serde_known::deserialize_in(
+                IntoDeserializer::<Error>::into_deserializer(string),
+                &MODES,
+            )
+        };
+        assert_eq!(deserialize("known_fast").unwrap(), sym!(known_fast));
+        assert_eq!(deserialize("known_safe").unwrap(), sym!(known_safe));
+        let err = deserialize("known_unsafe").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value: string \"known_unsafe\", expected one of `known_fast`, `known_safe`"
+        );
+        assert!(Symbol::get("known_unsafe").is_none());
+    }
+
+    #[test]
+    #[cfg(not(any(miri, target_arch = "wasm32")))]
+    fn crate_symbols() {
+        let hello = sym!(crate_symbols_hello);
+        let _ = static_sym!(crate_symbols_world);
+        let symbols = crate_symbols!();
+        assert!(symbols.len() >= 2);
+        assert!(symbols.clone().any(|symbol| symbol == hello));
+        assert!(
+            symbols
+                .clone()
+                .any(|symbol| symbol == "crate_symbols_world")
+        );
+        assert!(
+            !symbols
+                .clone()
+                .any(|symbol| symbol == "crate_symbols_missing")
+        );
+    }
+
+    #[test]
+    fn report_startup() {
+        let _ = sym!(report_startup);
+        let report = super::Registry::global().report_startup();
+        #[cfg(not(any(miri, target_arch = "wasm32")))]
+        {
+            assert!(report.sites >= crate_symbols!().len());
+            assert!(report.site_bytes >= report.sites * 8);
+        }
+        assert!(report.symbols >= 1);
+        assert!(report.string_bytes >= "report_startup".len());
+        assert!(format!("{report}").contains(" call sites ("));
+    }
+
+    #[test]
+    #[cfg(feature = "phf")]
+    fn perfect_symbol_set() {
+        #[derive(Debug, PartialEq)]
+        enum Op {
+            Add,
+            Sub,
+        }
+
+        perfect_symbol_set! {
+            static BUILTINS: PerfectSymbolSet<Op> = {
+                "add" => Op::Add,
+                "sub" => Op::Sub,
+            };
+            static EMPTY: PerfectSymbolSet<u32> = {};
+        }
+
+        assert_eq!(BUILTINS.lookup(sym!(add)), Some(&Op::Add));
+        assert_eq!(BUILTINS.lookup(Symbol::new("sub")), Some(&Op::Sub));
+        assert_eq!(BUILTINS.lookup(sym!(mul)), None);
+        assert_eq!(BUILTINS.lookup_str("add"), Some(&Op::Add));
+        assert!(BUILTINS.contains(sym!(sub)));
+        assert_eq!(BUILTINS.len(), 2);
+        assert!(EMPTY.is_empty());
+        assert_eq!(EMPTY.lookup(sym!(add)), None);
+    }
+
+    #[test]
+    fn static_borrow() {
+        use core::borrow::Borrow;
+        fn takes(x: impl Borrow<Symbol>) -> Symbol {
+            *x.borrow()
+        }
+        fn takes_ref(x: impl AsRef<Symbol>) -> Symbol {
+            *x.as_ref()
+        }
+        const BORROWED: StaticSymbol = static_sym!(borrowed);
+        let symbol = sym!(borrowed);
+        let symbol_ref: &Symbol = &symbol;
+        let static_ref: &StaticSymbol = &BORROWED;
+        assert_eq!(takes(symbol), symbol);
+        assert_eq!(takes(symbol_ref), symbol);
+        assert_eq!(takes(BORROWED), symbol);
+        assert_eq!(takes(static_ref), symbol);
+        assert_eq!(takes_ref(BORROWED), symbol);
+        assert_eq!(takes_ref(static_ref), symbol);
+    }
+
+    #[test]
+    fn statics_in_const_contexts() {
+        struct Keys {
+            first: StaticSymbol,
+            rest: [StaticSymbol; 2],
+        }
+        const KEYS: Keys = Keys {
+            first: static_sym!(const_a),
+            rest: [static_sym!(const_b), static_sym!(const_c)],
+        };
+
+        const fn make_key() -> StaticSymbol {
+            static_sym!(const_fn)
+        }
+        const FROM_CONST_FN: StaticSymbol = make_key();
+
+        trait Named {
+            const NAME: StaticSymbol = static_sym!(trait_default);
+        }
+        struct Default;
+        impl Named for Default {}
+        struct Overridden;
+        impl Named for Overridden {
+            const NAME: StaticSymbol = static_sym!(trait_impl);
+        }
+
+        struct Generic<T, const N: usize>(core::marker::PhantomData<T>);
+        impl<T, const N: usize> Generic<T, N> {
+            const NAME: StaticSymbol = static_sym!(generic);
+            const NAMES: [StaticSymbol; N] = [static_sym!(generic_array); N];
+        }
+
+        assert_eq!(KEYS.first, sym!(const_a));
+        assert_eq!(KEYS.rest[0], sym!(const_b));
+        assert_eq!(KEYS.rest[1], sym!(const_c));
+        assert_eq!(FROM_CONST_FN, sym!(const_fn));
+        assert_eq!(make_key(), sym!(const_fn));
+        assert_eq!(Default::NAME, sym!(trait_default));
+        assert_eq!(Overridden::NAME, sym!(trait_impl));
+        assert_eq!(Generic::<u8, 1>::NAME, sym!(generic));
+        assert_eq!(Generic::<u16, 2>::NAME, sym!(generic));
+        assert_eq!(Generic::<u8, 3>::NAMES, [sym!(generic_array); 3]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "introspection", not(any(miri, target_arch = "wasm32"))))]
+    fn sites_for() {
+        fn site_a() -> Symbol {
+            sym!(introspected)
+        }
+        fn site_b() -> Symbol {
+            sym!(introspected)
+        }
+        let symbol = site_a();
+        assert_eq!(site_b(), symbol);
+        assert_eq!(super::Registry::global().sites_for(symbol).len(), 2);
+        assert_eq!(
+            super::Registry::global()
+                .sites_for(sym!(introspected_once))
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "track-caller-sites", not(any(miri, target_arch = "wasm32"))))]
+    fn site_location() {
+        let line = line!() + 1;
+        let symbol = sym!(site_location_unique);
+        assert_eq!(
+            super::Registry::global().site_location(symbol),
+            Some((file!(), line))
+        );
+        assert_eq!(
+            super::Registry::global().site_location(Symbol::new("site_location_runtime")),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    fn assert_same_symbol() {
+        const SAME: StaticSymbol = static_sym!(assert_same_symbol);
+        assert_same_symbol!(sym!(assert_same_symbol), SAME);
+        assert_same_symbol!(Symbol::new("assert_same_symbol"), sym!(assert_same_symbol),);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn symbol_newtype() {
+        symbol_newtype! {
+            /// Component names.
+            pub struct ComponentName;
+            struct EntityName;
+        }
+
+        let transform = ComponentName::new("transform");
+        assert_eq!(transform, ComponentName::from_symbol(sym!(transform)));
+        assert_eq!(transform, ComponentName::new_static(&"transform"));
+        assert_eq!(ComponentName::get("transform"), Some(transform));
+        assert_eq!(ComponentName::get("newtype_unknown"), None);
+        assert_eq!(transform.symbol(), sym!(transform));
+        assert_eq!(Symbol::from(transform), sym!(transform));
+        assert_eq!(transform.as_str(), "transform");
+        assert_eq!(transform.as_ref(), "transform");
+        assert_eq!(format!("{transform}"), "transform");
+        assert_eq!(format!("{transform:?}"), "\"transform\"");
+
+        // Same string, but a different type.
+        let player = EntityName::new("transform");
+        assert_eq!(player.symbol(), transform.symbol());
+        assert_eq!(size_of::<EntityName>(), size_of::<Symbol>());
+
+        let mut map = HashMap::new();
+        map.insert(transform, 1);
+        assert_eq!(map.get(&ComponentName::new("transform")), Some(&1));
+    }
+
+    #[test]
+    fn static_warmup() {
+        static WARM: [StaticSymbol; 2] = [static_sym!(warm_a), static_sym!(warm_b)];
+        StaticSymbol::warmup(&WARM);
+        StaticSymbol::warmup(&[]);
+        assert_eq!(WARM[0], sym!(warm_a));
+        assert_eq!(WARM[1], sym!(warm_b));
+    }
+
+    #[test]
+    fn match_guards() {
+        const ADD: StaticSymbol = static_sym!(match_add);
+        const SUB: StaticSymbol = static_sym!(match_sub);
+
+        fn op(symbol: Symbol) -> Option<i32> {
+            match symbol {
+                s if s == ADD => Some(1),
+                s if s == SUB => Some(-1),
+                s if s == sym!(match_nop) => Some(0),
+                _ => None,
+            }
+        }
+
+        assert_eq!(op(sym!(match_add)), Some(1));
+        assert_eq!(op(Symbol::new_static(&"match_sub")), Some(-1));
+        assert_eq!(op(sym!(match_nop)), Some(0));
+        assert_eq!(op(sym!(match_mul)), None);
+
+        assert!(matches!(sym!(match_add), s if s == ADD || s == SUB));
+        assert!(!matches!(sym!(match_mul), s if s == ADD || s == SUB));
+
+        let maybe = Some(sym!(match_sub));
+        let found = if let Some(s) = maybe
+            && s == SUB
+        {
+            Some(s)
+        } else {
+            None
+        };
+        assert_eq!(found, Some(sym!(match_sub)));
+        let Some(s) = maybe.filter(|&s| s == SUB) else {
+            panic!("expected `match_sub`");
+        };
+        assert_eq!(s, SUB);
+    }
+
+    #[test]
+    fn static_assert_unique() {
+        const TABLE: [(StaticSymbol, u32); 3] = [
+            (static_sym!(unique_a), 1),
+            (static_sym!(unique_b), 2),
+            (static_sym!(unique_c), 3),
+        ];
+        StaticSymbol::assert_unique(&TABLE.map(|(key, _)| key));
+    }
+
+    #[test]
+    #[should_panic = "duplicate symbol \"unique_a\" at index 0 and 2"]
+    fn static_assert_unique_duplicate() {
+        StaticSymbol::assert_unique(&[
+            static_sym!(unique_a),
+            static_sym!(unique_b),
+            static_sym!("unique_a"),
+        ]);
+    }
 }
@@ -8,3 +8,7 @@ enable!();
 pub fn symbols_a_b() -> [Symbol; 2] {
     [sym!(a), sym!(b)]
 }
+
+pub fn plugin_symbols() -> [Symbol; 2] {
+    [sym!("plugin.load"), sym!("plugin.unload")]
+}
@@ -1,6 +1,6 @@
 #![cfg(all(test, not(miri)))]
 
-use stringleton::{Symbol, sym};
+use stringleton::{Registry, Symbol, sym};
 
 stringleton::enable!();
 
@@ -10,6 +10,19 @@ fn static_symbols_from_linked_dylib() {
     assert_eq!(syms, [sym!(a), sym!(b)]);
 }
 
+#[test]
+fn dylib_symbols_merged_into_registry() {
+    // The call sites of the dylib are registered when it is loaded, without
+    // calling into it.
+    let plugin: Vec<&str> = Registry::global()
+        .read()
+        .sorted_strings()
+        .into_iter()
+        .filter(|string| string.starts_with("plugin."))
+        .collect();
+    assert_eq!(plugin, ["plugin.load", "plugin.unload"]);
+}
+
 #[allow(improper_ctypes)] // This is fine because it's the same Rust compiler on both sides.
 unsafe extern "C" {
     fn cdylib_symbols_a_b(syms: &mut [Symbol; 2]);
@@ -0,0 +1,25 @@
+use stringleton::{StaticSymbol, Symbol, static_sym, sym};
+
+// Symbols in this file are registered by calling `_stringleton_register_symbols()`.
+stringleton::enable!(manual);
+
+const MANUAL: StaticSymbol = static_sym!(manual_static);
+
+fn manual() -> Symbol {
+    sym!(manual)
+}
+
+#[test]
+fn manual_registration() {
+    // With `debug-assertions`, using a call site before registration panics.
+    assert!(std::panic::catch_unwind(manual).is_err());
+    unsafe {
+        _stringleton_register_symbols();
+    }
+    assert_eq!(manual(), Symbol::new("manual"));
+    assert_eq!(MANUAL, Symbol::new("manual_static"));
+    unsafe {
+        _stringleton_register_symbols();
+    }
+    assert_eq!(manual(), Symbol::new("manual"));
+}
@@ -0,0 +1,16 @@
+use foreign_crate::bar;
+use foreign_crate_registry::foo;
+use stringleton::sym;
+
+// Forwarding to both crates. Symbols in this file go to the table of the first.
+stringleton::enable!(foreign_crate, ::foreign_crate_registry,);
+
+#[test]
+fn multiple_forwarded_crates() {
+    assert_eq!(foo(), sym!(foo));
+    assert_eq!(bar(), sym!(bar));
+    assert_eq!(
+        _stringleton_enabled::TABLE.as_ptr(),
+        _stringleton_forwarded::_stringleton_enabled::TABLE.as_ptr()
+    );
+}
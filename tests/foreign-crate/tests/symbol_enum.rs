@@ -0,0 +1,51 @@
+use stringleton::{Symbol, SymbolEnum, sym};
+
+stringleton::enable!(foreign_crate);
+
+#[derive(SymbolEnum, Debug, PartialEq, Clone, Copy)]
+enum Event {
+    MouseDown,
+    MouseUp,
+    #[symbol = "key-press"]
+    KeyPress,
+    HTTPRequest,
+}
+
+#[derive(SymbolEnum, Debug, PartialEq)]
+#[symbol(crate = ::stringleton)]
+enum Renamed {
+    #[symbol = "Hello, World!"]
+    Hello,
+}
+
+#[test]
+fn symbol() {
+    assert_eq!(Event::MouseDown.symbol(), sym!(mouse_down));
+    assert_eq!(Event::MouseUp.symbol(), sym!(mouse_up));
+    assert_eq!(Event::KeyPress.symbol(), sym!("key-press"));
+    assert_eq!(Event::HTTPRequest.symbol(), sym!(http_request));
+    assert_eq!(Renamed::Hello.symbol(), sym!("Hello, World!"));
+}
+
+#[test]
+fn from_symbol() {
+    for event in [
+        Event::MouseDown,
+        Event::MouseUp,
+        Event::KeyPress,
+        Event::HTTPRequest,
+    ] {
+        assert_eq!(Event::from_symbol(event.symbol()), Some(event));
+    }
+    assert_eq!(Event::from_symbol(sym!("key-press")), Some(Event::KeyPress));
+    assert_eq!(
+        Event::from_symbol(Symbol::new("mouse_up")),
+        Some(Event::MouseUp)
+    );
+    assert_eq!(Event::from_symbol(sym!(key_press)), None);
+    assert_eq!(Event::from_symbol(sym!(MouseDown)), None);
+    assert_eq!(
+        Renamed::from_symbol(sym!("Hello, World!")),
+        Some(Renamed::Hello)
+    );
+}